@@ -0,0 +1,95 @@
+use crate::context_files::{
+    explain_context_path, AliasRegistry, AliasSource, ContextFileStore, ContextResolution,
+};
+use crate::project_fs::SafePath;
+use serde::Serialize;
+
+/// A context key for `mx aliases`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AliasEntry {
+    pub key: String,
+    /// Path under `.mx/`.
+    pub path: String,
+    pub source: AliasSource,
+    /// Whether the file currently exists in `.mx/`.
+    pub exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolveOutcome {
+    #[serde(flatten)]
+    pub resolution: ContextResolution,
+    pub exists: bool,
+    /// Why the key cannot be used, when the resolved path is not safe.
+    pub error: Option<String>,
+}
+
+pub fn list(aliases: &AliasRegistry, store: &dyn ContextFileStore) -> Vec<AliasEntry> {
+    aliases
+        .aliases()
+        .map(|alias| AliasEntry {
+            key: alias.key.clone(),
+            path: alias.path.clone(),
+            source: alias.source,
+            exists: SafePath::try_from_path(alias.path.as_ref())
+                .is_ok_and(|path| store.context_file_exists(&path)),
+        })
+        .collect()
+}
+
+/// Explains how `key` resolves, using the same rules as every command that takes a context key.
+pub fn resolve(key: &str, aliases: &AliasRegistry, store: &dyn ContextFileStore) -> ResolveOutcome {
+    let resolution = explain_context_path(key, aliases);
+    let checked = SafePath::try_from_path(key.as_ref())
+        .and_then(|_| SafePath::try_from_path(&resolution.path));
+    let (exists, error) = match checked {
+        Ok(path) => (store.context_file_exists(&path), None),
+        Err(err) => (false, Some(err.to_string())),
+    };
+    ResolveOutcome { resolution, exists, error }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context_files::{resolve_context_path_with, InMemoryContextStore, ResolutionStep};
+
+    fn store_with(key: &str, aliases: &AliasRegistry) -> InMemoryContextStore {
+        let store = InMemoryContextStore::default();
+        let path = SafePath::try_from_path(&resolve_context_path_with(key, aliases)).unwrap();
+        let status = store.prepare_context_file(&path, false).unwrap();
+        store.write_context_contents(&status.path, "content").unwrap();
+        store
+    }
+
+    #[test]
+    fn list_reports_every_alias_and_whether_its_file_exists() {
+        let aliases = AliasRegistry::builtin();
+        let store = store_with("tk", &aliases);
+
+        let entries = list(&aliases, &store);
+
+        assert_eq!(entries.len(), aliases.aliases().count());
+        let tk = entries.iter().find(|entry| entry.key == "tk").unwrap();
+        assert_eq!(
+            (tk.path.as_str(), tk.source, tk.exists),
+            ("tasks.md", AliasSource::Builtin, true)
+        );
+        assert!(!entries.iter().find(|entry| entry.key == "rq").unwrap().exists);
+    }
+
+    #[test]
+    fn resolve_explains_the_key_and_flags_unsafe_ones() {
+        let aliases = AliasRegistry::builtin();
+        let store = store_with("pd-rq", &aliases);
+
+        let pending = resolve("pd-rq", &aliases, &store);
+        assert!(pending.exists);
+        assert_eq!(pending.resolution.path.to_str(), Some("pending/requirements.md"));
+        assert!(matches!(pending.resolution.steps[0], ResolutionStep::PendingPrefix { .. }));
+
+        let unsafe_key = resolve("../secret", &aliases, &store);
+        assert!(unsafe_key.error.is_some());
+        assert!(!unsafe_key.exists);
+    }
+}
@@ -1,9 +1,12 @@
-use crate::context_files::resolve_validated_context_path;
-use crate::context_files::ContextFileStore;
+use crate::context_files::{resolve_validated_context_path_with, AliasRegistry, ContextFileStore};
 use crate::error::AppError;
 
-pub fn execute(key: &str, store: &dyn ContextFileStore) -> Result<String, AppError> {
-    let relative_path = resolve_validated_context_path(key)?;
+pub fn execute(
+    key: &str,
+    store: &dyn ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<String, AppError> {
+    let relative_path = resolve_validated_context_path_with(key, aliases)?;
     store.read_context_contents(&relative_path)
 }
 
@@ -25,14 +28,15 @@ mod tests {
             .write_context_contents(&status.path, "task body")
             .expect("context file should be written");
 
-        let content = execute("tk", &store).expect("cat command should succeed");
+        let content =
+            execute("tk", &store, &AliasRegistry::builtin()).expect("cat command should succeed");
         assert_eq!(content, "task body");
     }
 
     #[test]
     fn execute_rejects_path_traversal() {
         let store = InMemoryContextStore::default();
-        let result = execute("../secret", &store);
+        let result = execute("../secret", &store, &AliasRegistry::builtin());
         assert!(matches!(
             result,
             Err(AppError::PathTraversal(crate::error::PathTraversalError::Detected(_)))
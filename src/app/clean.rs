@@ -1,5 +1,4 @@
-use crate::context_files::resolve_validated_context_path;
-use crate::context_files::ContextFileStore;
+use crate::context_files::{resolve_validated_context_path_with, AliasRegistry, ContextFileStore};
 use crate::error::AppError;
 
 #[derive(Debug, Clone)]
@@ -10,6 +9,7 @@ pub struct CleanOutcome {
 pub fn execute(
     key: Option<String>,
     store: &dyn ContextFileStore,
+    aliases: &AliasRegistry,
 ) -> Result<CleanOutcome, AppError> {
     match key {
         None => {
@@ -20,7 +20,7 @@ pub fn execute(
             }
         }
         Some(key) => {
-            let relative_path = resolve_validated_context_path(&key)?;
+            let relative_path = resolve_validated_context_path_with(&key, aliases)?;
             let target_path = store.remove_context_file(&relative_path)?;
             Ok(CleanOutcome { message: format!("Removed {}", target_path.display()) })
         }
@@ -42,7 +42,8 @@ mod tests {
         let status = store.prepare_context_file(&safe_path, false).unwrap();
         store.write_context_contents(&status.path, "content").unwrap();
 
-        let outcome = execute(None, &store).expect("clean command should succeed");
+        let outcome =
+            execute(None, &store, &AliasRegistry::builtin()).expect("clean command should succeed");
         assert_eq!(outcome.message, "Cleared .mx directory contents");
     }
 
@@ -54,8 +55,8 @@ mod tests {
         let status = store.prepare_context_file(&safe_path, false).unwrap();
         store.write_context_contents(&status.path, "content").unwrap();
 
-        let outcome =
-            execute(Some("tk".to_string()), &store).expect("targeted clean should succeed");
+        let outcome = execute(Some("tk".to_string()), &store, &AliasRegistry::builtin())
+            .expect("targeted clean should succeed");
         assert!(outcome.message.contains(".mx/tasks.md"));
     }
 }
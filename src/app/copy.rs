@@ -1,8 +1,14 @@
 use crate::clipboard::Clipboard;
-use crate::error::AppError;
+use crate::context_files::{resolve_validated_context_path_with, AliasRegistry, ContextFileStore};
+use crate::error::{AppError, ConfigError};
 use crate::project_fs::{SafePath, WorkspaceFileReader};
-use crate::snippets::{strip_frontmatter, SnippetCatalog};
+use crate::snippets::{
+    block_overrides, classify_placeholder, read_snippet_metadata, render_blocks, strip_frontmatter,
+    variant_language, BlockError, Deprecation, Placeholder, Requirement, SnippetCatalog,
+    SnippetEntry, SnippetFrontmatter,
+};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -11,27 +17,252 @@ pub struct CopyOutcome {
     pub snippet: String,
     pub relative_path: String,
     pub absolute_path: std::path::PathBuf,
+    /// Language of the variant that was copied, when it was not the base file.
+    pub language: Option<String>,
+    /// Entries of the snippet's `requires:` that were missing or empty.
+    pub missing_requirements: Vec<MissingRequirement>,
+    /// Set when the snippet's front matter marks it deprecated.
+    pub deprecation: Option<Deprecation>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingRequirement {
+    pub requirement: Requirement,
+    /// Workspace-relative path that was checked.
+    pub path: String,
+    pub problem: RequirementProblem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequirementProblem {
+    Missing,
+    Empty,
+    Invalid(String),
+}
+
+impl MissingRequirement {
+    /// The command that creates the missing input, when mx can create it.
+    pub fn hint(&self) -> Option<String> {
+        match (&self.requirement, &self.problem) {
+            (
+                Requirement::Context(key),
+                RequirementProblem::Missing | RequirementProblem::Empty,
+            ) => Some(format!("mx t {key}")),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MissingRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.problem {
+            RequirementProblem::Missing => write!(f, "{} is missing", self.path)?,
+            RequirementProblem::Empty => write!(f, "{} is empty", self.path)?,
+            RequirementProblem::Invalid(reason) => write!(f, "{} is invalid: {reason}", self.path)?,
+        }
+        if let Some(hint) = self.hint() {
+            write!(f, " (run `{hint}`)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Copies the expanded snippet to the clipboard. Inputs listed under `requires:` are checked
+/// first; with `strict` any missing input aborts the copy.
 pub fn execute(
     snippet: &str,
     catalog: &dyn SnippetCatalog,
     clipboard: &dyn Clipboard,
     workspace_files: Option<&dyn WorkspaceFileReader>,
+    context_files: Option<&dyn ContextFileStore>,
+    aliases: &AliasRegistry,
+    strict: bool,
 ) -> Result<CopyOutcome, AppError> {
-    let snippet_entry = catalog.resolve_snippet(snippet)?;
+    let snippet_entry = catalog.localize(catalog.resolve_snippet(snippet)?);
     let raw = fs::read_to_string(&snippet_entry.absolute_path)?;
-    let content = strip_frontmatter(&raw);
-    let expanded = expand_placeholders(content, workspace_files);
+    let (metadata, content) = render_snippet(&snippet_entry, &raw, catalog)?;
+    let requirements = metadata.requires.clone().unwrap_or_default();
+    let missing_requirements =
+        check_requirements(&requirements, workspace_files, context_files, aliases);
+    if strict && !missing_requirements.is_empty() {
+        let summary: Vec<String> = missing_requirements.iter().map(ToString::to_string).collect();
+        return Err(AppError::ConfigError(ConfigError::MissingRequirements(summary.join(", "))));
+    }
+
+    let mut include_stack = vec![snippet_entry.relative_path.clone()];
+    let included = expand_includes(&content, catalog, &mut include_stack);
+    let expanded = expand_placeholders(included.as_ref(), workspace_files);
     clipboard.copy(expanded.as_ref())?;
 
+    let language = variant_language(&snippet_entry.absolute_path);
     Ok(CopyOutcome {
         snippet: snippet_entry.key,
         relative_path: snippet_entry.relative_path,
         absolute_path: snippet_entry.absolute_path,
+        language,
+        missing_requirements,
+        deprecation: metadata.deprecation().cloned(),
     })
 }
 
+/// Metadata and body of a snippet after `extends:` is applied: the chain of parents is walked
+/// up to the root template, whose body is rendered with the blocks overridden along the way.
+/// The child's metadata and blocks win over its parents'.
+fn render_snippet(
+    entry: &SnippetEntry,
+    raw: &str,
+    catalog: &dyn SnippetCatalog,
+) -> Result<(SnippetFrontmatter, String), AppError> {
+    let mut metadata = snippet_metadata(entry, raw);
+    let mut parent_query = metadata.extends.clone();
+    let mut body = strip_frontmatter(raw).to_string();
+    let mut body_path = entry.relative_path.clone();
+    let mut chain = vec![entry.relative_path.clone()];
+    let mut overrides = BTreeMap::new();
+
+    while let Some(query) = parent_query {
+        for (name, block) in block_overrides(&body).map_err(|err| block_error(&body_path, err))? {
+            overrides.entry(name).or_insert(block);
+        }
+
+        let parent = catalog.localize(catalog.resolve_snippet(&query)?);
+        if chain.contains(&parent.relative_path) {
+            return Err(AppError::ConfigError(ConfigError::Other(format!(
+                "Snippet inheritance cycle: {} -> {}",
+                chain.join(" -> "),
+                parent.relative_path
+            ))));
+        }
+        chain.push(parent.relative_path.clone());
+
+        let parent_raw = fs::read_to_string(&parent.absolute_path)?;
+        let parent_metadata = snippet_metadata(&parent, &parent_raw);
+        parent_query = parent_metadata.extends.clone();
+        metadata = metadata.inherit(parent_metadata);
+        body = strip_frontmatter(&parent_raw).to_string();
+        body_path = parent.relative_path;
+    }
+
+    let rendered = render_blocks(&body, &overrides).map_err(|err| block_error(&body_path, err))?;
+    Ok((metadata, rendered))
+}
+
+/// Broken front matter is reported by `mx lint`; copying still works without metadata.
+fn snippet_metadata(entry: &SnippetEntry, raw: &str) -> SnippetFrontmatter {
+    read_snippet_metadata(&entry.absolute_path, raw).ok().flatten().unwrap_or_default()
+}
+
+fn block_error(relative_path: &str, err: BlockError) -> AppError {
+    AppError::ConfigError(ConfigError::Other(format!("Invalid blocks in {relative_path}: {err}")))
+}
+
+fn check_requirements(
+    requirements: &[Requirement],
+    workspace_files: Option<&dyn WorkspaceFileReader>,
+    context_files: Option<&dyn ContextFileStore>,
+    aliases: &AliasRegistry,
+) -> Vec<MissingRequirement> {
+    let mut missing = Vec::new();
+    for requirement in requirements {
+        let (path, result) = match requirement {
+            Requirement::Context(key) => match resolve_validated_context_path_with(key, aliases) {
+                Ok(relative) => {
+                    let contents = context_files
+                        .map(|store| store.read_context_contents(&relative).ok())
+                        .unwrap_or_default();
+                    (format!(".mx/{}", relative.display()), Ok(contents))
+                }
+                Err(err) => (key.clone(), Err(err.to_string())),
+            },
+            Requirement::WorkspaceFile { path } => match SafePath::try_from_path(Path::new(path)) {
+                Ok(relative) => {
+                    let contents =
+                        workspace_files.and_then(|store| store.read_workspace_file(&relative).ok());
+                    (path.clone(), Ok(contents))
+                }
+                Err(err) => (path.clone(), Err(err.to_string())),
+            },
+        };
+
+        let problem = match result {
+            Err(reason) => RequirementProblem::Invalid(reason),
+            Ok(None) => RequirementProblem::Missing,
+            Ok(Some(contents)) if contents.trim().is_empty() => RequirementProblem::Empty,
+            Ok(Some(_)) => continue,
+        };
+        missing.push(MissingRequirement { requirement: requirement.clone(), path, problem });
+    }
+    missing
+}
+
+/// Inlines `{{snippet:...}}` includes recursively. `stack` holds the relative paths of the
+/// snippets currently being expanded so include cycles are reported instead of followed.
+fn expand_includes<'a>(
+    content: &'a str,
+    catalog: &dyn SnippetCatalog,
+    stack: &mut Vec<String>,
+) -> Cow<'a, str> {
+    if !content.contains("{{") {
+        return Cow::Borrowed(content);
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut parts = content.split("{{");
+    output.push_str(parts.next().unwrap_or_default());
+
+    for part in parts {
+        let Some((token, rest)) = part.split_once("}}") else {
+            output.push_str("{{");
+            output.push_str(part);
+            continue;
+        };
+
+        match classify_placeholder(token) {
+            Placeholder::SnippetInclude(query) => {
+                output.push_str(&render_include(query, catalog, stack));
+            }
+            Placeholder::WorkspaceFile(_) | Placeholder::BlockStart(_) | Placeholder::BlockEnd => {
+                output.push_str("{{");
+                output.push_str(token);
+                output.push_str("}}");
+            }
+        }
+        output.push_str(rest);
+    }
+
+    Cow::Owned(output)
+}
+
+fn render_include(query: &str, catalog: &dyn SnippetCatalog, stack: &mut Vec<String>) -> String {
+    let entry = match catalog.resolve_snippet(query) {
+        Ok(entry) => catalog.localize(entry),
+        Err(err) => return format!("[mx missing: snippet:{query} ({err})]"),
+    };
+
+    if stack.contains(&entry.relative_path) {
+        return format!(
+            "[mx error: include cycle: {} -> {}]",
+            stack.join(" -> "),
+            entry.relative_path
+        );
+    }
+
+    let raw = match fs::read_to_string(&entry.absolute_path) {
+        Ok(raw) => raw,
+        Err(err) => return format!("[mx missing: snippet:{query} ({})]", err.kind()),
+    };
+
+    let body = match render_snippet(&entry, &raw, catalog) {
+        Ok((_, body)) => body,
+        Err(err) => return format!("[mx error: snippet:{query} ({err})]"),
+    };
+
+    stack.push(entry.relative_path);
+    let rendered = expand_includes(&body, catalog, stack).into_owned();
+    stack.pop();
+    rendered
+}
+
 fn expand_placeholders<'a>(
     content: &'a str,
     workspace_files: Option<&dyn WorkspaceFileReader>,
@@ -87,12 +318,19 @@ fn render_placeholder(raw_token: &str, workspace_files: &dyn WorkspaceFileReader
 mod tests {
     use super::*;
     use crate::clipboard::InMemoryClipboard;
+    use crate::context_files::{resolve_validated_context_path, InMemoryContextStore};
     use crate::project_fs::InMemoryWorkspaceFileReader;
-    use crate::snippets::InMemoryCatalog;
     use crate::snippets::SnippetEntry;
+    use crate::snippets::{catalog_with_files, InMemoryCatalog};
     use std::fs;
     use tempfile::TempDir;
 
+    fn write_context(store: &InMemoryContextStore, key: &str, contents: &str) {
+        let relative_path = resolve_validated_context_path(key).unwrap();
+        let status = store.prepare_context_file(&relative_path, true).unwrap();
+        store.write_context_contents(&status.path, contents).unwrap();
+    }
+
     fn build_catalog_with_snippet(
         contents: &str,
     ) -> (InMemoryCatalog, TempDir, std::path::PathBuf) {
@@ -117,8 +355,16 @@ mod tests {
         let workspace_files = InMemoryWorkspaceFileReader::default();
         workspace_files.set_file(".mx/info.md", "expanded");
 
-        let outcome = execute("wc", &catalog, &clipboard, Some(&workspace_files))
-            .expect("copy command should succeed");
+        let outcome = execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            None,
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect("copy command should succeed");
 
         assert_eq!(outcome.snippet, "wc");
         assert_eq!(outcome.relative_path, "w/wc");
@@ -131,7 +377,8 @@ mod tests {
         let (catalog, _dir, _) = build_catalog_with_snippet("{{.mx/info.md}}");
         let clipboard = InMemoryClipboard::default();
 
-        execute("wc", &catalog, &clipboard, None).expect("copy command should succeed");
+        execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+            .expect("copy command should succeed");
         assert_eq!(clipboard.contents(), "{{.mx/info.md}}");
     }
 
@@ -141,8 +388,16 @@ mod tests {
         let clipboard = InMemoryClipboard::default();
         let workspace_files = InMemoryWorkspaceFileReader::default();
 
-        let error = execute("unknown", &catalog, &clipboard, Some(&workspace_files))
-            .expect_err("missing snippet should fail");
+        let error = execute(
+            "unknown",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            None,
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect_err("missing snippet should fail");
         assert!(matches!(error, AppError::NotFound(crate::error::NotFoundError::Snippet(_))));
     }
 
@@ -152,8 +407,16 @@ mod tests {
         let clipboard = InMemoryClipboard::default();
         let workspace_files = InMemoryWorkspaceFileReader::default();
 
-        execute("wc", &catalog, &clipboard, Some(&workspace_files))
-            .expect("copy command should succeed with placeholder marker");
+        execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            None,
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect("copy command should succeed with placeholder marker");
         assert!(clipboard.contents().contains("[mx error:"));
     }
 
@@ -164,8 +427,16 @@ mod tests {
         let workspace_files = InMemoryWorkspaceFileReader::default();
         workspace_files.set_file(".mx/info.md", "expanded");
 
-        execute("wc", &catalog, &clipboard, Some(&workspace_files))
-            .expect("copy command should succeed");
+        execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            None,
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect("copy command should succeed");
         assert_eq!(clipboard.contents(), "prefix {{.mx/info.md");
     }
 
@@ -175,7 +446,8 @@ mod tests {
             build_catalog_with_snippet("---\ntitle: My Snippet\n---\nbody only\n");
         let clipboard = InMemoryClipboard::default();
 
-        execute("wc", &catalog, &clipboard, None).expect("copy should succeed");
+        execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+            .expect("copy should succeed");
         assert_eq!(clipboard.contents(), "body only\n");
     }
 
@@ -187,16 +459,192 @@ mod tests {
         let workspace_files = InMemoryWorkspaceFileReader::default();
         workspace_files.set_file(".mx/info.md", "injected");
 
-        execute("wc", &catalog, &clipboard, Some(&workspace_files)).expect("copy should succeed");
+        execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            None,
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect("copy should succeed");
         assert_eq!(clipboard.contents(), "header injected");
     }
 
+    #[test]
+    fn execute_renders_the_extends_chain_with_child_blocks_and_metadata() {
+        let (catalog, _dir) = catalog_with_files(&[
+            (
+                "base/review",
+                "---\nhidden: true\nrequires: [pl]\n---\nReview.\n{{#block checklist}}\n- generic\n{{/block}}\n{{#block tone}}Be kind.\n{{/block}}",
+            ),
+            (
+                "review/strict",
+                "---\nextends: base/review\n---\n{{#block tone}}Be blunt.\n{{/block}}",
+            ),
+            (
+                "review/security",
+                "---\nextends: review/strict\ndeprecated: true\n---\n{{#block checklist}}\n- injection\n{{/block}}",
+            ),
+        ]);
+        let clipboard = InMemoryClipboard::default();
+
+        let outcome =
+            execute("security", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+                .unwrap();
+
+        assert_eq!(clipboard.contents(), "Review.\n- injection\nBe blunt.\n");
+        assert_eq!(outcome.missing_requirements.len(), 1);
+        assert!(outcome.deprecation.is_some());
+    }
+
+    #[test]
+    fn execute_rejects_inheritance_cycles() {
+        let (catalog, _dir) = catalog_with_files(&[
+            ("a/one", "---\nextends: a/two\n---\n"),
+            ("a/two", "---\nextends: a/one\n---\n"),
+        ]);
+        let clipboard = InMemoryClipboard::default();
+
+        let err =
+            execute("one", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+                .unwrap_err();
+
+        assert!(err.to_string().contains("a/one -> a/two -> a/one"));
+        assert_eq!(clipboard.contents(), "");
+    }
+
+    #[test]
+    fn execute_inlines_snippet_includes_without_workspace_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let root_path = dir.path().join("w/wc.md");
+        let shared_path = dir.path().join("shared/checklist.md");
+        fs::create_dir_all(root_path.parent().unwrap()).unwrap();
+        fs::create_dir_all(shared_path.parent().unwrap()).unwrap();
+        fs::write(&root_path, "intro\n{{snippet:checklist}}outro").unwrap();
+        fs::write(&shared_path, "---\ntitle: Checklist\n---\n- item\n").unwrap();
+        let catalog = InMemoryCatalog::new(vec![
+            SnippetEntry {
+                key: "wc".to_string(),
+                relative_path: "w/wc".to_string(),
+                absolute_path: root_path,
+            },
+            SnippetEntry {
+                key: "checklist".to_string(),
+                relative_path: "shared/checklist".to_string(),
+                absolute_path: shared_path,
+            },
+        ]);
+        let clipboard = InMemoryClipboard::default();
+
+        execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+            .expect("copy should succeed");
+        assert_eq!(clipboard.contents(), "intro\n- item\noutro");
+    }
+
+    #[test]
+    fn execute_reports_include_cycles_and_missing_includes() {
+        let (catalog, _dir, _) =
+            build_catalog_with_snippet("{{snippet:w/wc}} and {{snippet:unknown}}");
+        let clipboard = InMemoryClipboard::default();
+
+        execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+            .expect("copy should succeed");
+        let contents = clipboard.contents();
+        assert!(contents.contains("[mx error: include cycle: w/wc -> w/wc]"));
+        assert!(contents.contains("[mx missing: snippet:unknown"));
+    }
+
+    #[test]
+    fn execute_reports_missing_and_empty_requirements_with_hints() {
+        let (catalog, _dir, _) = build_catalog_with_snippet(
+            "---\nrequires: [tk, rq, pl, {path: docs/spec.md}]\n---\nImplement the plan\n",
+        );
+        let clipboard = InMemoryClipboard::default();
+        let workspace_files = InMemoryWorkspaceFileReader::default();
+        let context_files = InMemoryContextStore::default();
+        write_context(&context_files, "rq", "   \n");
+        write_context(&context_files, "pl", "1. do it\n");
+
+        let outcome = execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            Some(&workspace_files),
+            Some(&context_files),
+            &AliasRegistry::builtin(),
+            false,
+        )
+        .expect("non-strict copy should succeed");
+
+        let reports: Vec<String> =
+            outcome.missing_requirements.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            reports,
+            vec![
+                ".mx/tasks.md is missing (run `mx t tk`)",
+                ".mx/requirements.md is empty (run `mx t rq`)",
+                "docs/spec.md is missing",
+            ]
+        );
+        assert_eq!(clipboard.contents(), "Implement the plan\n");
+    }
+
+    #[test]
+    fn execute_strict_refuses_to_copy_with_missing_requirements() {
+        let (catalog, _dir, _) =
+            build_catalog_with_snippet("---\nrequires: [tk]\n---\nImplement the plan\n");
+        let clipboard = InMemoryClipboard::default();
+        let context_files = InMemoryContextStore::default();
+
+        let err = execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            None,
+            Some(&context_files),
+            &AliasRegistry::builtin(),
+            true,
+        )
+        .expect_err("strict copy should fail");
+        assert!(err.to_string().contains("Missing required inputs: .mx/tasks.md is missing"));
+        assert_eq!(clipboard.contents(), "");
+
+        write_context(&context_files, "tk", "- task\n");
+        execute(
+            "wc",
+            &catalog,
+            &clipboard,
+            None,
+            Some(&context_files),
+            &AliasRegistry::builtin(),
+            true,
+        )
+        .expect("strict copy should succeed once inputs exist");
+    }
+
+    #[test]
+    fn execute_reports_deprecation_but_still_copies() {
+        let (catalog, _dir, _) = build_catalog_with_snippet(
+            "---\ndeprecated: {replacement: review/security, reason: merged}\n---\nold body\n",
+        );
+        let clipboard = InMemoryClipboard::default();
+
+        let outcome =
+            execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+                .expect("copy should succeed");
+        assert_eq!(outcome.deprecation.unwrap().replacement(), Some("review/security"));
+        assert_eq!(clipboard.contents(), "old body\n");
+    }
+
     #[test]
     fn execute_unaffected_when_no_frontmatter() {
         let (catalog, _dir, _) = build_catalog_with_snippet("plain body\n");
         let clipboard = InMemoryClipboard::default();
 
-        execute("wc", &catalog, &clipboard, None).expect("copy should succeed");
+        execute("wc", &catalog, &clipboard, None, None, &AliasRegistry::builtin(), false)
+            .expect("copy should succeed");
         assert_eq!(clipboard.contents(), "plain body\n");
     }
 }
@@ -0,0 +1,138 @@
+use crate::error::{AppError, NotFoundError};
+use crate::snippets::{SnippetCatalog, SnippetHistory};
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffOutcome {
+    pub relative_path: String,
+    pub revision: u64,
+    pub lines: Vec<DiffLine>,
+}
+
+impl DiffOutcome {
+    pub fn has_changes(&self) -> bool {
+        self.lines.iter().any(|line| !matches!(line, DiffLine::Same(_)))
+    }
+}
+
+/// Compares a recorded revision (by default the one before the current contents) with the
+/// snippet file as it is now.
+pub fn execute(
+    query: &str,
+    revision: Option<u64>,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<DiffOutcome, AppError> {
+    let entry = catalog.resolve_snippet(query)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "observed")?;
+
+    let revision = match revision {
+        Some(id) => id,
+        None => {
+            let revisions = history.revisions(&entry.relative_path)?;
+            let previous = revisions.len().checked_sub(2).map(|index| revisions[index].id);
+            previous.ok_or_else(|| {
+                AppError::NotFound(NotFoundError::File(format!(
+                    "No earlier revision recorded for {}",
+                    entry.relative_path
+                )))
+            })?
+        }
+    };
+
+    let old = history.read_revision(&entry.relative_path, revision)?;
+    let current = fs::read_to_string(&entry.absolute_path)?;
+    Ok(DiffOutcome {
+        relative_path: entry.relative_path,
+        revision,
+        lines: diff_lines(&old, &current),
+    })
+}
+
+/// Line-based diff using a longest-common-subsequence table.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn diff_lines_marks_removed_and_added_lines() {
+        let lines = diff_lines("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_defaults_to_previous_revision() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "old line\n")]);
+        let path = dir.path().join("w/wc.md");
+        let history = history_in(dir.path());
+
+        assert!(execute("wc", None, &catalog, &history).is_err());
+
+        fs::write(&path, "new line\n").unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+
+        let outcome = execute("wc", None, &catalog, &history).expect("diff should succeed");
+        assert_eq!(outcome.revision, 1);
+        assert!(outcome.has_changes());
+        assert_eq!(
+            outcome.lines,
+            vec![
+                DiffLine::Removed("old line".to_string()),
+                DiffLine::Added("new line".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::AppError;
+use crate::snippets::{SnippetCatalog, SnippetHistory};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct EditOutcome {
+    pub snippet: String,
+    pub relative_path: String,
+    pub absolute_path: PathBuf,
+}
+
+/// Resolves the snippet to edit, snapshotting its current contents so the edit can be undone.
+/// Snippets from synced sources are refused: the next `mx sync` would discard the edit.
+pub fn execute(
+    query: &str,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<EditOutcome, AppError> {
+    let entry = catalog.resolve_snippet(query)?;
+    super::ensure_local(&entry, commands_root)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "observed")?;
+    Ok(EditOutcome {
+        snippet: entry.key,
+        relative_path: entry.relative_path,
+        absolute_path: entry.absolute_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+
+    #[test]
+    fn execute_resolves_snippet_path_and_snapshots_it() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "before edit")]);
+        let path = dir.path().join("w/wc.md");
+        let history = history_in(dir.path());
+
+        let outcome =
+            execute("wc", &catalog, &history, dir.path()).expect("edit should resolve the snippet");
+        assert_eq!(outcome.absolute_path, path);
+        assert_eq!(history.read_revision("w/wc", 1).unwrap(), "before edit");
+
+        let mounted = dir.path().join("commands");
+        let err = execute("wc", &catalog, &history, &mounted).expect_err("synced snippet");
+        assert!(err.to_string().contains("read-only"));
+    }
+}
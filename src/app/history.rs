@@ -0,0 +1,39 @@
+use crate::error::AppError;
+use crate::snippets::{Revision, SnippetCatalog, SnippetHistory};
+
+#[derive(Debug, Clone)]
+pub struct HistoryOutcome {
+    pub snippet: String,
+    pub relative_path: String,
+    /// Snapshots oldest first; the last one matches the current file contents.
+    pub revisions: Vec<Revision>,
+}
+
+pub fn execute(
+    query: &str,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<HistoryOutcome, AppError> {
+    let entry = catalog.resolve_snippet(query)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "observed")?;
+    let revisions = history.revisions(&entry.relative_path)?;
+
+    Ok(HistoryOutcome { snippet: entry.key, relative_path: entry.relative_path, revisions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+
+    #[test]
+    fn execute_snapshots_current_contents_on_first_view() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body")]);
+        let history = history_in(dir.path());
+
+        let outcome = execute("wc", &catalog, &history).expect("history should succeed");
+        assert_eq!(outcome.relative_path, "w/wc");
+        assert_eq!(outcome.revisions.len(), 1);
+        assert_eq!(outcome.revisions[0].reason, "observed");
+    }
+}
@@ -0,0 +1,358 @@
+use crate::error::AppError;
+use crate::project_fs::SafePath;
+use crate::snippets::{
+    block_overrides, classify_placeholder, scan_placeholders, sidecar_path, strip_frontmatter,
+    validate_frontmatter, validate_sidecar, Placeholder, SnippetCatalog, SnippetEntry,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub relative_path: String,
+    /// 1-based line number within the snippet file, when the problem has a location.
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LintOutcome {
+    pub checked: usize,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintOutcome {
+    pub fn error_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error).count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Warning).count()
+    }
+}
+
+pub fn execute(catalog: &dyn SnippetCatalog) -> Result<LintOutcome, AppError> {
+    let snippets = catalog.enumerate_all_snippets()?;
+    let mut diagnostics = Vec::new();
+
+    // Hidden snippets only resolve by path, so their keys cannot be ambiguous.
+    check_key_collisions(&catalog.enumerate_snippets()?, &mut diagnostics);
+
+    let mut bodies: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for snippet in &snippets {
+        let content = match fs::read_to_string(&snippet.absolute_path) {
+            Ok(content) => content,
+            Err(err) => {
+                diagnostics.push(error(snippet, None, format!("unreadable file: {err}")));
+                continue;
+            }
+        };
+
+        check_frontmatter(snippet, &content, catalog, &mut diagnostics);
+        check_placeholders(snippet, &content, catalog, &mut diagnostics);
+
+        let body = strip_frontmatter(&content).trim();
+        if !body.is_empty() {
+            bodies.entry(body.to_string()).or_default().push(&snippet.relative_path);
+        }
+    }
+
+    for paths in bodies.values().filter(|paths| paths.len() > 1) {
+        for (index, relative_path) in paths.iter().enumerate() {
+            let others: Vec<&str> =
+                paths.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, p)| *p).collect();
+            diagnostics.push(LintDiagnostic {
+                relative_path: relative_path.to_string(),
+                line: None,
+                severity: Severity::Warning,
+                message: format!("body is identical to {}", others.join(", ")),
+            });
+        }
+    }
+
+    diagnostics.sort_by(|a, b| {
+        a.relative_path
+            .cmp(&b.relative_path)
+            .then(a.line.cmp(&b.line))
+            .then(a.severity.cmp(&b.severity))
+    });
+    Ok(LintOutcome { checked: snippets.len(), diagnostics })
+}
+
+fn check_key_collisions(snippets: &[SnippetEntry], diagnostics: &mut Vec<LintDiagnostic>) {
+    let mut by_key: BTreeMap<&str, Vec<&SnippetEntry>> = BTreeMap::new();
+    for snippet in snippets {
+        by_key.entry(snippet.key.as_str()).or_default().push(snippet);
+    }
+
+    for (key, entries) in by_key.into_iter().filter(|(_, entries)| entries.len() > 1) {
+        let paths: Vec<&str> = entries.iter().map(|entry| entry.relative_path.as_str()).collect();
+        for entry in entries {
+            diagnostics.push(error(
+                entry,
+                None,
+                format!("key '{key}' is ambiguous; shared by {}", paths.join(", ")),
+            ));
+        }
+    }
+}
+
+fn check_frontmatter(
+    snippet: &SnippetEntry,
+    content: &str,
+    catalog: &dyn SnippetCatalog,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let sidecar = sidecar_path(&snippet.absolute_path);
+    let embedded = validate_frontmatter(content);
+    let (validated, sidecar_name) = match embedded {
+        Ok(None) => match validate_sidecar(&snippet.absolute_path) {
+            Ok(Some(validated)) => {
+                (validated, sidecar.file_name().map(|name| name.to_string_lossy()))
+            }
+            Ok(None) => return,
+            Err(err) => {
+                let name = sidecar.file_name().unwrap_or_default().to_string_lossy();
+                diagnostics.push(error(
+                    snippet,
+                    None,
+                    format!("invalid metadata in {name}: {err}"),
+                ));
+                return;
+            }
+        },
+        Ok(Some(validated)) => {
+            if sidecar.is_file() {
+                diagnostics.push(LintDiagnostic {
+                    relative_path: snippet.relative_path.clone(),
+                    line: Some(1),
+                    severity: Severity::Warning,
+                    message: "front matter shadows the sidecar metadata file".to_string(),
+                });
+            }
+            (validated, None)
+        }
+        Err(err) => {
+            // The block starts on the line after the opening fence.
+            let line = err.line().map(|line| line + 1).or(Some(1));
+            diagnostics.push(error(snippet, line, format!("invalid frontmatter: {err}")));
+            return;
+        }
+    };
+
+    for warning in validated.warnings {
+        // Lines inside a sidecar do not map onto the snippet file.
+        let (line, message) = match &sidecar_name {
+            Some(name) => {
+                let at = warning.line.map(|line| format!(":{line}")).unwrap_or_default();
+                (None, format!("{} (in {name}{at})", warning.message))
+            }
+            None => (warning.line, warning.message),
+        };
+        diagnostics.push(LintDiagnostic {
+            relative_path: snippet.relative_path.clone(),
+            line,
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    if let Some(parent) = &validated.metadata.extends {
+        if let Err(err) = catalog.resolve_snippet(parent) {
+            diagnostics.push(error(
+                snippet,
+                None,
+                format!("extends '{parent}' does not resolve: {err}"),
+            ));
+        }
+    }
+
+    let replacement = validated.metadata.deprecation().and_then(|d| d.replacement());
+    if let Some(replacement) = replacement {
+        if let Err(err) = catalog.resolve_snippet(replacement) {
+            diagnostics.push(LintDiagnostic {
+                relative_path: snippet.relative_path.clone(),
+                line: None,
+                severity: Severity::Warning,
+                message: format!("deprecated replacement '{replacement}' does not resolve: {err}"),
+            });
+        }
+    }
+}
+
+fn check_placeholders(
+    snippet: &SnippetEntry,
+    content: &str,
+    catalog: &dyn SnippetCatalog,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let body = strip_frontmatter(content);
+    let body_start = content.len() - body.len();
+    if let Err(err) = block_overrides(body) {
+        diagnostics.push(error(snippet, None, format!("invalid blocks: {err}")));
+    }
+
+    for placeholder in scan_placeholders(body) {
+        let line = content[..body_start + placeholder.offset].matches('\n').count() + 1;
+        let Some(token) = placeholder.token else {
+            diagnostics.push(error(snippet, Some(line), "unclosed '{{' placeholder".to_string()));
+            continue;
+        };
+
+        match classify_placeholder(token) {
+            Placeholder::WorkspaceFile("") => {}
+            Placeholder::WorkspaceFile(path) => {
+                if let Err(err) = SafePath::try_from_path(Path::new(path)) {
+                    diagnostics.push(error(
+                        snippet,
+                        Some(line),
+                        format!("placeholder '{{{{{path}}}}}' is not a safe path: {err}"),
+                    ));
+                }
+            }
+            Placeholder::BlockStart(_) | Placeholder::BlockEnd => {}
+            Placeholder::SnippetInclude(query) => {
+                if let Err(err) = catalog.resolve_snippet(query) {
+                    diagnostics.push(error(
+                        snippet,
+                        Some(line),
+                        format!("include 'snippet:{query}' does not resolve: {err}"),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn error(snippet: &SnippetEntry, line: Option<usize>, message: String) -> LintDiagnostic {
+    LintDiagnostic {
+        relative_path: snippet.relative_path.clone(),
+        line,
+        severity: Severity::Error,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::catalog_with_files;
+
+    fn messages(outcome: &LintOutcome) -> Vec<(String, Option<usize>, Severity)> {
+        outcome.diagnostics.iter().map(|d| (d.relative_path.clone(), d.line, d.severity)).collect()
+    }
+
+    #[test]
+    fn execute_accepts_clean_library() {
+        let (catalog, _dir) = catalog_with_files(&[
+            ("w/wc", "---\ntitle: Work\n---\nUse {{.mx/tasks.md}} and {{snippet:rq}}\n"),
+            ("sdd/rq", "Requirements\n"),
+        ]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(outcome.checked, 2);
+        assert!(outcome.diagnostics.is_empty(), "{:?}", outcome.diagnostics);
+    }
+
+    #[test]
+    fn execute_reports_invalid_frontmatter_with_line() {
+        let (catalog, _dir) =
+            catalog_with_files(&[("w/wc", "---\ntitle: ok\ndescription: [unclosed\n---\nbody\n")]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(outcome.error_count(), 1);
+        assert!(outcome.diagnostics[0].message.starts_with("invalid frontmatter"));
+        assert!(outcome.diagnostics[0].line.is_some());
+    }
+
+    #[test]
+    fn execute_warns_about_unknown_fields() {
+        let (catalog, _dir) =
+            catalog_with_files(&[("w/wc", "---\ntitle: T\ncolour: red\n---\nbody\n")]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(messages(&outcome), vec![("w/wc".to_string(), Some(3), Severity::Warning)]);
+        assert_eq!(outcome.error_count(), 0);
+    }
+
+    #[test]
+    fn execute_checks_extends_targets_and_block_markers() {
+        let (catalog, _dir) = catalog_with_files(&[
+            ("base/review", "Review\n{{#block checklist}}\n- item\n{{/block}}\n"),
+            ("r/ok", "---\nextends: base/review\n---\n{{#block checklist}}x{{/block}}"),
+            ("r/lost", "---\nextends: base/missing\n---\n"),
+            ("r/open", "{{#block checklist}}\n- never closed\n"),
+        ]);
+
+        let outcome = execute(&catalog).unwrap();
+
+        let errors: Vec<(&str, &str)> = outcome
+            .diagnostics
+            .iter()
+            .map(|d| (d.relative_path.as_str(), d.message.as_str()))
+            .collect();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].0 == "r/lost" && errors[0].1.starts_with("extends 'base/missing'"));
+        assert!(errors[1].0 == "r/open" && errors[1].1.starts_with("invalid blocks"));
+    }
+
+    #[test]
+    fn execute_warns_about_unresolvable_deprecation_replacement() {
+        let (catalog, _dir) = catalog_with_files(&[
+            ("w/old", "---\ndeprecated: {replacement: w/new}\n---\nold\n"),
+            ("w/older", "---\ndeprecated: {replacement: w/missing}\n---\nolder\n"),
+            ("w/new", "new\n"),
+        ]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(messages(&outcome), vec![("w/older".to_string(), None, Severity::Warning)]);
+        assert!(outcome.diagnostics[0].message.contains("'w/missing' does not resolve"));
+    }
+
+    #[test]
+    fn execute_reports_key_collisions() {
+        let (catalog, _dir) = catalog_with_files(&[("a/wc", "one\n"), ("b/wc", "two\n")]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(outcome.error_count(), 2);
+        assert!(outcome.diagnostics[0].message.contains("key 'wc' is ambiguous"));
+    }
+
+    #[test]
+    fn execute_reports_placeholder_problems_with_lines() {
+        let (catalog, _dir) = catalog_with_files(&[(
+            "w/wc",
+            "---\ntitle: T\n---\nline one\n{{../secret}}\n{{snippet:missing}}\n{{unclosed\n",
+        )]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(
+            messages(&outcome),
+            vec![
+                ("w/wc".to_string(), Some(5), Severity::Error),
+                ("w/wc".to_string(), Some(6), Severity::Error),
+                ("w/wc".to_string(), Some(7), Severity::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_warns_about_identical_bodies() {
+        let (catalog, _dir) = catalog_with_files(&[
+            ("a/one", "same body\n"),
+            ("b/two", "---\ntitle: T\n---\nsame body\n"),
+        ]);
+
+        let outcome = execute(&catalog).unwrap();
+        assert_eq!(outcome.warning_count(), 2);
+        assert!(outcome.diagnostics[0].message.contains("identical to b/two"));
+    }
+}
@@ -1,56 +1,138 @@
 use crate::error::{AppError, ConfigError};
-use crate::snippets::{parse_frontmatter_metadata, SnippetCatalog};
+use crate::snippets::{
+    read_snippet_metadata, variant_files, SnippetCatalog, SnippetEntry, UsageStats,
+};
+use serde::Serialize;
+use std::cmp::Ordering;
 use std::fs;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ListEntry {
     pub snippet: String,
     pub relative_path: String,
     pub title: Option<String>,
     pub description: Option<String>,
+    /// How often the snippet was copied, from the usage log. `None` when usage wasn't loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uses: Option<u64>,
+    /// Seconds since the Unix epoch of the last copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u64>,
+    /// Languages of the snippet's variants, such as `ja` for `wc.ja.md`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
 }
 
-pub fn execute(catalog: &dyn SnippetCatalog) -> Result<Vec<ListEntry>, AppError> {
-    let snippets = catalog.enumerate_snippets()?;
-
-    let mut entries: Vec<ListEntry> = snippets
-        .into_iter()
-        .map(|snippet| {
-            let content = fs::read_to_string(&snippet.absolute_path)?;
-            let (title, description) = match parse_frontmatter_metadata(&content) {
-                Ok(Some(fm)) => (fm.title, fm.description),
-                Ok(None) => (None, None),
-                Err(e) => {
-                    return Err(ConfigError::Other(format!(
-                        "Failed to parse frontmatter in snippet: {}: {}",
-                        snippet.absolute_path.display(),
-                        e
-                    ))
-                    .into())
-                }
-            };
-
-            Ok(ListEntry {
-                snippet: snippet.key,
+/// Order of `mx list`. Ties fall back to the snippet name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    #[default]
+    Name,
+    /// Most recently copied first.
+    Recent,
+    /// Most often copied first.
+    Frequent,
+}
+
+/// A snippet that could not be listed, reported instead of failing the whole listing.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListDiagnostic {
+    pub relative_path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListOutcome {
+    pub entries: Vec<ListEntry>,
+    pub diagnostics: Vec<ListDiagnostic>,
+}
+
+/// Lists every snippet with its metadata. With `strict`, the first unreadable file or invalid
+/// front matter aborts the listing; otherwise such snippets are skipped and reported in
+/// [`ListOutcome::diagnostics`]. Without `usage`, entries carry no usage fields and the usage
+/// sorts fall back to the snippet name.
+pub fn execute(
+    catalog: &dyn SnippetCatalog,
+    strict: bool,
+    sort: ListSort,
+    usage: Option<&UsageStats>,
+) -> Result<ListOutcome, AppError> {
+    let mut outcome = ListOutcome::default();
+    let snippets = if strict {
+        catalog.enumerate_snippets()?
+    } else {
+        let (snippets, problems) = catalog.enumerate_snippets_leniently()?;
+        outcome.diagnostics.extend(problems.into_iter().map(|problem| ListDiagnostic {
+            relative_path: problem.path,
+            message: problem.message,
+        }));
+        snippets
+    };
+
+    for snippet in snippets {
+        match list_entry(&snippet, usage) {
+            Ok(entry) => outcome.entries.push(entry),
+            Err(err) if strict => return Err(err),
+            Err(err) => outcome.diagnostics.push(ListDiagnostic {
                 relative_path: snippet.relative_path,
-                title,
-                description,
-            })
-        })
-        .collect::<Result<Vec<_>, AppError>>()?;
-
-    entries.sort_by(|a, b| a.snippet.cmp(&b.snippet));
-    Ok(entries)
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    outcome.entries.sort_by(|a, b| {
+        let by_usage = match sort {
+            ListSort::Name => Ordering::Equal,
+            ListSort::Recent => b.last_used.cmp(&a.last_used),
+            ListSort::Frequent => b.uses.cmp(&a.uses),
+        };
+        by_usage.then_with(|| a.snippet.cmp(&b.snippet))
+    });
+    Ok(outcome)
+}
+
+fn list_entry(snippet: &SnippetEntry, usage: Option<&UsageStats>) -> Result<ListEntry, AppError> {
+    let content = fs::read_to_string(&snippet.absolute_path)?;
+    let (title, description) = match read_snippet_metadata(&snippet.absolute_path, &content) {
+        Ok(Some(fm)) => (fm.title, fm.description),
+        Ok(None) => (None, None),
+        Err(e) => {
+            return Err(ConfigError::Other(format!(
+                "Failed to parse frontmatter in snippet: {}: {}",
+                snippet.absolute_path.display(),
+                e
+            ))
+            .into())
+        }
+    };
+
+    let usage = usage.map(|stats| stats.get(&snippet.relative_path));
+    Ok(ListEntry {
+        snippet: snippet.key.clone(),
+        relative_path: snippet.relative_path.clone(),
+        title,
+        description,
+        uses: usage.map(|usage| usage.uses),
+        last_used: usage.and_then(|usage| usage.last_used),
+        variants: variant_files(&snippet.absolute_path)
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::snippets::InMemoryCatalog;
     use crate::snippets::SnippetEntry;
+    use crate::snippets::{InMemoryCatalog, UsageEvent};
     use std::fs;
     use tempfile::TempDir;
 
+    fn list(catalog: &InMemoryCatalog, strict: bool) -> Result<ListOutcome, AppError> {
+        execute(catalog, strict, ListSort::Name, None)
+    }
+
     fn entry_with_file(key: &str, rel: &str, content: &str) -> (SnippetEntry, TempDir) {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join(format!("{key}.md"));
@@ -71,7 +153,7 @@ mod tests {
         let (e2, _d2) = entry_with_file("aa", "a/aa", "");
         let catalog = InMemoryCatalog::new(vec![e1, e2]);
 
-        let entries = execute(&catalog).expect("list command should succeed");
+        let entries = list(&catalog, false).expect("list command should succeed").entries;
         assert_eq!(entries.len(), 2);
         assert_eq!(entries[0].snippet, "aa");
         assert_eq!(entries[1].snippet, "wc");
@@ -86,7 +168,7 @@ mod tests {
         );
         let catalog = InMemoryCatalog::new(vec![e]);
 
-        let entries = execute(&catalog).unwrap();
+        let entries = list(&catalog, false).unwrap().entries;
         assert_eq!(entries[0].title.as_deref(), Some("Work on Tasks"));
         assert_eq!(entries[0].description.as_deref(), Some("Critical planning"));
     }
@@ -96,7 +178,7 @@ mod tests {
         let (e, _dir) = entry_with_file("wc", "w/wc", "plain body\n");
         let catalog = InMemoryCatalog::new(vec![e]);
 
-        let entries = execute(&catalog).unwrap();
+        let entries = list(&catalog, false).unwrap().entries;
         assert!(entries[0].title.is_none());
         assert!(entries[0].description.is_none());
     }
@@ -107,7 +189,7 @@ mod tests {
         let (e2, _d2) = entry_with_file("zz", "z/zz", "no frontmatter\n");
         let catalog = InMemoryCatalog::new(vec![e1, e2]);
 
-        let entries = execute(&catalog).unwrap();
+        let entries = list(&catalog, false).unwrap().entries;
         assert_eq!(entries[0].snippet, "aa");
         assert_eq!(entries[0].title.as_deref(), Some("AA"));
         assert_eq!(entries[1].snippet, "zz");
@@ -121,7 +203,7 @@ mod tests {
             relative_path: "w/wc".to_string(),
             absolute_path: std::path::PathBuf::from("does_not_exist.md"),
         }]);
-        let result = execute(&catalog);
+        let result = list(&catalog, true);
         assert!(result.is_err());
     }
 
@@ -130,9 +212,73 @@ mod tests {
         let (e, _dir) = entry_with_file("wc", "w/wc", "---\ntitle: [unclosed array\n---\nbody\n");
         let catalog = InMemoryCatalog::new(vec![e]);
 
-        let result = execute(&catalog);
+        let result = list(&catalog, true);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.to_string().contains("Failed to parse frontmatter in snippet"));
     }
+
+    #[test]
+    fn execute_collects_diagnostics_instead_of_failing_when_not_strict() {
+        let (good, _d1) = entry_with_file("aa", "a/aa", "---\ntitle: AA\n---\nbody\n");
+        let (bad, _d2) = entry_with_file("wc", "w/wc", "---\ntitle: [unclosed array\n---\nbody\n");
+        let missing = SnippetEntry {
+            key: "zz".to_string(),
+            relative_path: "z/zz".to_string(),
+            absolute_path: std::path::PathBuf::from("does_not_exist.md"),
+        };
+        let catalog = InMemoryCatalog::new(vec![good, bad, missing]);
+
+        let outcome = list(&catalog, false).expect("resilient listing should succeed");
+        assert_eq!(outcome.entries.len(), 1);
+        assert_eq!(outcome.entries[0].snippet, "aa");
+        assert_eq!(outcome.diagnostics.len(), 2);
+        assert_eq!(outcome.diagnostics[0].relative_path, "w/wc");
+        assert!(outcome.diagnostics[0].message.contains("Failed to parse frontmatter"));
+        assert_eq!(outcome.diagnostics[1].relative_path, "z/zz");
+    }
+
+    #[test]
+    fn execute_sorts_by_recent_or_frequent_use() {
+        let (e1, _d1) = entry_with_file("aa", "a/aa", "");
+        let (e2, _d2) = entry_with_file("wc", "w/wc", "");
+        let (e3, _d3) = entry_with_file("zz", "z/zz", "");
+        let catalog = InMemoryCatalog::new(vec![e1, e2, e3]);
+        let event = |relative_path: &str, used_at: u64| UsageEvent {
+            relative_path: relative_path.to_string(),
+            key: String::new(),
+            used_at,
+            workspace: None,
+        };
+        let usage =
+            UsageStats::from_events(&[event("w/wc", 10), event("w/wc", 20), event("z/zz", 30)], 30);
+
+        let names = |sort| -> Vec<String> {
+            execute(&catalog, false, sort, Some(&usage))
+                .unwrap()
+                .entries
+                .into_iter()
+                .map(|entry| entry.snippet)
+                .collect()
+        };
+        assert_eq!(names(ListSort::Recent), vec!["zz", "wc", "aa"]);
+        assert_eq!(names(ListSort::Frequent), vec!["wc", "zz", "aa"]);
+        assert_eq!(names(ListSort::Name), vec!["aa", "wc", "zz"]);
+    }
+
+    #[test]
+    fn execute_leaves_usage_fields_out_when_usage_is_not_loaded() {
+        let (e, _dir) = entry_with_file("wc", "w/wc", "");
+        let catalog = InMemoryCatalog::new(vec![e]);
+
+        let entry = &list(&catalog, false).unwrap().entries[0];
+        assert_eq!(entry.uses, None);
+        let json = serde_json::to_value(entry).unwrap();
+        assert!(json.get("uses").is_none());
+        assert!(json.get("last_used").is_none());
+
+        let usage = UsageStats::from_events(&[], 0);
+        let outcome = execute(&catalog, false, ListSort::Name, Some(&usage)).unwrap();
+        assert_eq!(outcome.entries[0].uses, Some(0));
+    }
 }
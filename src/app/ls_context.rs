@@ -0,0 +1,74 @@
+use crate::context_files::{context_key_for, AliasRegistry, ContextFileStore};
+use crate::error::AppError;
+use serde::Serialize;
+
+/// A file under `.mx/` for `mx ls-context`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextFileEntry {
+    /// Path under `.mx/`, with `/` separators.
+    pub path: String,
+    /// The context key that resolves to this file, if there is one.
+    pub key: Option<String>,
+    pub size: u64,
+    pub lines: usize,
+    /// Seconds since the Unix epoch.
+    pub modified: Option<u64>,
+}
+
+pub fn execute(
+    store: &dyn ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<Vec<ContextFileEntry>, AppError> {
+    let entries = store
+        .list_context_files()?
+        .into_iter()
+        .map(|file| ContextFileEntry {
+            path: file
+                .relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            key: context_key_for(&file.relative_path, aliases),
+            size: file.size,
+            lines: file.lines,
+            modified: file.modified,
+        })
+        .collect();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context_files::InMemoryContextStore;
+    use crate::project_fs::SafePath;
+    use std::path::Path;
+
+    #[test]
+    fn execute_lists_files_with_their_keys() {
+        let store = InMemoryContextStore::default();
+        for (path, contents) in
+            [("tasks.md", "a\nb\n"), ("docs/spec.md", "spec"), ("pending/requirements.md", "")]
+        {
+            let path = SafePath::try_from_path(Path::new(path)).unwrap();
+            let status = store.prepare_context_file(&path, false).unwrap();
+            store.write_context_contents(&status.path, contents).unwrap();
+        }
+
+        let entries = execute(&store, &AliasRegistry::builtin()).unwrap();
+
+        let summary: Vec<(&str, Option<&str>, u64, usize)> = entries
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.key.as_deref(), entry.size, entry.lines))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("docs/spec.md", None, 4, 1),
+                ("pending/requirements.md", Some("pdr"), 0, 0),
+                ("tasks.md", Some("tk"), 4, 2),
+            ]
+        );
+    }
+}
@@ -1,38 +1,119 @@
+pub mod aliases;
 pub mod cat;
 pub mod clean;
 pub mod copy;
+pub mod diff;
+pub mod edit;
+pub mod history;
+pub mod lint;
 pub mod list;
+pub mod ls_context;
+pub mod mv;
+pub mod new;
+pub mod pack;
+pub mod pin;
+pub mod restore;
+pub mod rm;
+pub mod search;
+pub mod stats;
+pub mod sync;
 pub mod touch;
+pub mod trash;
 pub mod which;
 
-use crate::clipboard::clipboard_from_env;
-use crate::context_files::ContextFileStore;
-use crate::error::AppError;
+use crate::clipboard::{clipboard_from_env, CommandSource, ContentSource, FileSource, StdinSource};
+use crate::context_files::{AliasRegistry, ContextFileStore};
+use crate::error::{AppError, ConfigError};
 use crate::project_fs::WorkspaceFileReader;
-use crate::snippets::SnippetCatalog;
-use std::path::Path;
+use crate::snippets::{
+    sidecar_path, variant_files, variant_path, PackManifest, Pin, PinStore, SnippetCatalog,
+    SnippetEntry, SnippetHistory, UsageStats,
+};
+use crate::sources::GitSource;
+use std::path::{Path, PathBuf};
 
+pub use aliases::{AliasEntry, ResolveOutcome};
 pub use clean::CleanOutcome;
-pub use copy::CopyOutcome;
-pub use list::ListEntry;
-pub use touch::TouchOutcome;
+pub use copy::{CopyOutcome, MissingRequirement, RequirementProblem};
+pub use diff::{DiffLine, DiffOutcome};
+pub use edit::EditOutcome;
+pub use history::HistoryOutcome;
+pub use lint::{LintDiagnostic, LintOutcome, Severity};
+pub use list::{ListDiagnostic, ListEntry, ListOutcome, ListSort};
+pub use ls_context::ContextFileEntry;
+pub use mv::MoveOutcome;
+pub use new::{NewOutcome, NewSnippetOptions, SnippetSource};
+pub use pack::{
+    ConflictStrategy, ImportAction, ImportedEntry, PackExportOptions, PackImportOptions,
+    PackImportOutcome,
+};
+pub use pin::{PinOutcome, PinnedEntry};
+pub use restore::RestoreOutcome;
+pub use rm::RemoveOutcome;
+pub use search::{SearchField, SearchHit, SearchMatch, SearchOptions};
+pub use stats::{StatsEntry, StatsOutcome};
+pub use sync::SourceSyncResult;
+pub use touch::{AppendSeparator, TouchMode, TouchOutcome, TouchSource};
+pub use trash::{ContextRestoreOutcome, TrashEntry};
 pub use which::WhichOutcome;
 
-pub fn cat_context(key: &str, store: &impl ContextFileStore) -> Result<String, AppError> {
-    cat::execute(key, store)
+pub fn context_aliases(aliases: &AliasRegistry, store: &impl ContextFileStore) -> Vec<AliasEntry> {
+    aliases::list(aliases, store)
+}
+
+pub fn resolve_context_key(
+    key: &str,
+    aliases: &AliasRegistry,
+    store: &impl ContextFileStore,
+) -> ResolveOutcome {
+    aliases::resolve(key, aliases, store)
+}
+
+pub fn list_context_files(
+    store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<Vec<ContextFileEntry>, AppError> {
+    ls_context::execute(store, aliases)
+}
+
+pub fn list_trash(
+    store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<Vec<TrashEntry>, AppError> {
+    trash::list(store, aliases)
+}
+
+pub fn restore_context(
+    key: &str,
+    store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<ContextRestoreOutcome, AppError> {
+    trash::restore(key, store, aliases)
+}
+
+pub fn cat_context(
+    key: &str,
+    store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<String, AppError> {
+    cat::execute(key, store, aliases)
 }
 
 pub fn clean_context(
     key: Option<String>,
     store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
 ) -> Result<CleanOutcome, AppError> {
-    clean::execute(key, store)
+    clean::execute(key, store, aliases)
 }
 
 pub fn copy_snippet(
     snippet: &str,
     catalog: &impl SnippetCatalog,
     workspace_files: Option<&impl WorkspaceFileReader>,
+    context_files: Option<&impl ContextFileStore>,
+    aliases: &AliasRegistry,
+    strict: bool,
 ) -> Result<CopyOutcome, AppError> {
     let clipboard = clipboard_from_env()?;
     copy::execute(
@@ -40,20 +121,184 @@ pub fn copy_snippet(
         catalog,
         clipboard.as_ref(),
         workspace_files.map(|store| store as &dyn WorkspaceFileReader),
+        context_files.map(|store| store as &dyn ContextFileStore),
+        aliases,
+        strict,
     )
 }
 
-pub fn list_snippets(catalog: &impl SnippetCatalog) -> Result<Vec<ListEntry>, AppError> {
-    list::execute(catalog)
+pub fn diff_snippet(
+    snippet: &str,
+    revision: Option<u64>,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<DiffOutcome, AppError> {
+    diff::execute(snippet, revision, catalog, history)
+}
+
+pub fn edit_snippet(
+    snippet: &str,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<EditOutcome, AppError> {
+    edit::execute(snippet, catalog, history, commands_root)
+}
+
+pub fn snippet_history(
+    snippet: &str,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<HistoryOutcome, AppError> {
+    history::execute(snippet, catalog, history)
+}
+
+pub fn lint_snippets(catalog: &impl SnippetCatalog) -> Result<LintOutcome, AppError> {
+    lint::execute(catalog)
+}
+
+pub fn list_snippets(
+    catalog: &impl SnippetCatalog,
+    strict: bool,
+    sort: ListSort,
+    usage: Option<&UsageStats>,
+) -> Result<ListOutcome, AppError> {
+    list::execute(catalog, strict, sort, usage)
+}
+
+pub fn move_snippet(
+    from: &str,
+    to: &str,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<MoveOutcome, AppError> {
+    mv::execute(from, to, catalog, history, commands_root)
+}
+
+pub fn new_snippet(
+    name: &str,
+    source: SnippetSource,
+    options: &NewSnippetOptions,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<NewOutcome, AppError> {
+    let body = match source {
+        SnippetSource::Scaffold => None,
+        SnippetSource::Clipboard => Some(clipboard_from_env()?.paste()?),
+        SnippetSource::Text(text) => Some(text),
+    };
+    new::execute(name, body.as_deref(), options, catalog, history, commands_root)
+}
+
+pub fn export_pack(
+    selector: &str,
+    output: &Path,
+    options: &PackExportOptions,
+    catalog: &impl SnippetCatalog,
+    commands_root: &Path,
+) -> Result<PackManifest, AppError> {
+    pack::export(selector, output, options, catalog, commands_root)
+}
+
+pub fn import_pack(
+    input: &Path,
+    options: &PackImportOptions,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<PackImportOutcome, AppError> {
+    pack::import(input, options, catalog, history, commands_root)
+}
+
+pub fn remove_snippet(
+    snippet: &str,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+    trash_root: &Path,
+) -> Result<RemoveOutcome, AppError> {
+    rm::execute(snippet, catalog, history, commands_root, trash_root)
+}
+
+pub fn restore_snippet(
+    snippet: &str,
+    revision: u64,
+    catalog: &impl SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<RestoreOutcome, AppError> {
+    restore::execute(snippet, revision, catalog, history)
+}
+
+pub fn search_snippets(
+    query: &str,
+    options: &SearchOptions,
+    catalog: &impl SnippetCatalog,
+    roots: &[PathBuf],
+    usage: &UsageStats,
+) -> Result<Vec<SearchHit>, AppError> {
+    search::execute(query, options, catalog, roots, usage)
+}
+
+pub fn pin_snippet(
+    slot: u8,
+    snippet: &str,
+    catalog: &impl SnippetCatalog,
+    store: &PinStore,
+) -> Result<PinOutcome, AppError> {
+    pin::pin(slot, snippet, catalog, store)
+}
+
+pub fn unpin_snippet(slot: u8, store: &PinStore) -> Result<Pin, AppError> {
+    pin::unpin(slot, store)
+}
+
+pub fn pinned_snippets(
+    catalog: &impl SnippetCatalog,
+    store: &PinStore,
+) -> Result<Vec<PinnedEntry>, AppError> {
+    pin::list(catalog, store)
+}
+
+pub fn resolve_pinned(
+    query: &str,
+    catalog: &impl SnippetCatalog,
+    store: &PinStore,
+) -> Result<Option<SnippetEntry>, AppError> {
+    pin::resolve_query(query, catalog, store)
+}
+
+pub fn snippet_stats(
+    catalog: &impl SnippetCatalog,
+    usage: &UsageStats,
+) -> Result<StatsOutcome, AppError> {
+    stats::execute(catalog, usage)
+}
+
+pub fn sync_sources(
+    sources: &[GitSource],
+    only: Option<&str>,
+    sources_root: &Path,
+) -> Result<Vec<SourceSyncResult>, AppError> {
+    sync::execute(sources, only, sources_root)
 }
 
 pub fn touch_context(
     key: &str,
-    force: bool,
+    mode: &TouchMode,
+    source: TouchSource,
     store: &impl ContextFileStore,
+    aliases: &AliasRegistry,
 ) -> Result<TouchOutcome, AppError> {
-    let clipboard = clipboard_from_env()?;
-    touch::execute(key, force, store, clipboard.as_ref())
+    // The clipboard is only looked up when asked for, so headless machines never need one.
+    let source: Box<dyn ContentSource> = match source {
+        TouchSource::Clipboard => Box::new(clipboard_from_env()?),
+        TouchSource::Stdin => Box::new(StdinSource),
+        TouchSource::File(path) => Box::new(FileSource::new(path)),
+        TouchSource::Command(command) => Box::new(CommandSource::new(command)),
+    };
+    touch::execute(key, mode, store, source.as_ref(), aliases)
 }
 
 pub fn which_path(
@@ -63,3 +308,40 @@ pub fn which_path(
 ) -> Result<WhichOutcome, AppError> {
     which::execute(query, catalog, commands_root)
 }
+
+/// Snippets mounted from a synced source live outside the commands root and are replaced on
+/// every `mx sync`, so commands that move or delete files refuse to touch them.
+pub(crate) fn ensure_local(entry: &SnippetEntry, commands_root: &Path) -> Result<(), AppError> {
+    if entry.absolute_path.starts_with(commands_root) {
+        return Ok(());
+    }
+    Err(AppError::ConfigError(ConfigError::Other(format!(
+        "'{}' comes from a synced source and is read-only",
+        entry.relative_path
+    ))))
+}
+
+/// Moves a snippet file together with its `.meta.yaml` sidecar and language variants. Nothing
+/// moves when any target already exists, even one the catalog does not list, such as an
+/// ignored or hidden file.
+pub(crate) fn move_snippet_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    let mut moves = vec![(from.to_path_buf(), to.to_path_buf())];
+    let sidecar = sidecar_path(from);
+    if sidecar.is_file() {
+        moves.push((sidecar, sidecar_path(to)));
+    }
+    for (language, variant) in variant_files(from) {
+        moves.push((variant, variant_path(to, &language)));
+    }
+
+    if let Some((_, taken)) = moves.iter().find(|(_, target)| target.symlink_metadata().is_ok()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", taken.display()),
+        ));
+    }
+    for (source, target) in moves {
+        crate::project_fs::move_file(&source, &target)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use crate::error::{AppError, ConfigError};
+use crate::project_fs::prune_empty_dirs;
+use crate::snippets::{
+    candidate_key, conflicting_snippets, normalize_query, rewrite_extends, rewrite_sidecar_extends,
+    rewrite_snippet_includes, sidecar_path, SnippetCatalog, SnippetHistory,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct MoveOutcome {
+    pub from: String,
+    pub to: String,
+    /// Key the snippet resolves by at its new path.
+    pub key: String,
+    pub absolute_path: PathBuf,
+    /// Relative paths of the snippets whose `{{snippet:...}}` includes or `extends:` references
+    /// were rewritten.
+    pub updated_snippets: Vec<String>,
+    /// Snippets whose references could not be rewritten after the move, with the reason.
+    pub failed_updates: Vec<(String, String)>,
+}
+
+/// Moves a snippet and its history, then rewrites the includes and `extends:` references (in
+/// front matter or a sidecar) that point at it. Every rewrite is
+/// planned before anything moves, so an unreadable snippet stops the move with nothing changed.
+/// Every file mx touches is snapshotted before and after the change.
+pub fn execute(
+    from: &str,
+    to: &str,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<MoveOutcome, AppError> {
+    let source = catalog.resolve_snippet(from)?;
+    super::ensure_local(&source, commands_root)?;
+    let target = normalize_query(to)?.to_string();
+    if target == source.relative_path {
+        return Err(AppError::ConfigError(ConfigError::Other(format!(
+            "Snippet is already at {target}"
+        ))));
+    }
+
+    let existing = catalog.enumerate_all_snippets()?;
+    let conflicts: Vec<_> = conflicting_snippets(&existing, &target)
+        .into_iter()
+        .filter(|entry| entry.relative_path != source.relative_path)
+        .map(|entry| entry.relative_path.as_str())
+        .collect();
+    if !conflicts.is_empty() {
+        return Err(AppError::ConfigError(ConfigError::DuplicateSnippet(format!(
+            "'{target}' would collide with existing snippets: {}",
+            conflicts.join(", ")
+        ))));
+    }
+
+    let target_path = commands_root.join(format!("{target}.md"));
+    let old_key = source.key.as_str();
+    let new_key = candidate_key(&target);
+    let rewrite_reference = |query: &str| {
+        let normalized = normalize_query(query).ok()?.to_string();
+        if normalized == source.relative_path {
+            Some(target.clone())
+        } else if normalized == old_key && old_key != new_key {
+            Some(new_key.clone())
+        } else {
+            None
+        }
+    };
+
+    // Each planned rewrite is (snippet, file, new contents, whether the file is a sidecar).
+    let mut rewrites = Vec::new();
+    for entry in existing.iter().filter(|entry| entry.absolute_path.starts_with(commands_root)) {
+        // The moved snippet may refer to itself; it is rewritten at its new path.
+        let (relative_path, path) = if entry.relative_path == source.relative_path {
+            (target.clone(), target_path.clone())
+        } else {
+            (entry.relative_path.clone(), entry.absolute_path.clone())
+        };
+
+        let content = fs::read_to_string(&entry.absolute_path)?;
+        let includes = rewrite_snippet_includes(&content, rewrite_reference);
+        let extended = rewrite_extends(includes.as_deref().unwrap_or(&content), rewrite_reference);
+        if let Some(rewritten) = extended.or(includes) {
+            rewrites.push((relative_path.clone(), path.clone(), rewritten, false));
+        }
+
+        let sidecar = sidecar_path(&entry.absolute_path);
+        if sidecar.is_file() {
+            let yaml = fs::read_to_string(&sidecar)?;
+            if let Some(rewritten) = rewrite_sidecar_extends(&yaml, rewrite_reference) {
+                rewrites.push((relative_path, sidecar_path(&path), rewritten, true));
+            }
+        }
+    }
+
+    history.observe(&source.relative_path, &source.absolute_path, "observed")?;
+    // History follows the file only once it has moved, so a failed move leaves both in place.
+    super::move_snippet_file(&source.absolute_path, &target_path)?;
+    history.rename(&source.relative_path, &target)?;
+    history.observe(&target, &target_path, "mv")?;
+    if let Some(parent) = source.absolute_path.parent() {
+        prune_empty_dirs(parent, commands_root);
+    }
+
+    let mut updated_snippets = Vec::new();
+    let mut failed_updates = Vec::new();
+    for (relative_path, path, rewritten, sidecar) in rewrites {
+        // History records snippet files; sidecars are written as they are.
+        let written = if sidecar {
+            fs::write(&path, rewritten).map_err(AppError::from)
+        } else {
+            history
+                .observe(&relative_path, &path, "observed")
+                .and_then(|_| fs::write(&path, rewritten).map_err(AppError::from))
+                .and_then(|_| history.observe(&relative_path, &path, "mv").map(|_| ()))
+        };
+        match written {
+            Ok(()) if updated_snippets.contains(&relative_path) => {}
+            Ok(()) => updated_snippets.push(relative_path),
+            Err(err) => failed_updates.push((relative_path, err.to_string())),
+        }
+    }
+
+    Ok(MoveOutcome {
+        from: source.relative_path,
+        to: target,
+        key: new_key,
+        absolute_path: target_path,
+        updated_snippets,
+        failed_updates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+
+    #[test]
+    fn execute_moves_file_and_rewrites_includes() {
+        let (catalog, dir) = catalog_with_files(&[
+            ("w/wc", "body"),
+            ("r/review", "{{snippet:w/wc}} and {{snippet:wc}} and {{.mx/info.md}}"),
+            ("r/other", "{{snippet:unrelated}}"),
+        ]);
+
+        let outcome =
+            execute("wc", "work/work-critically", &catalog, &history_in(dir.path()), dir.path())
+                .expect("mv should succeed");
+
+        assert_eq!(outcome.from, "w/wc");
+        assert_eq!(outcome.to, "work/work-critically");
+        assert_eq!(outcome.key, "work-critically");
+        assert_eq!(outcome.updated_snippets, vec!["r/review"]);
+        assert!(!dir.path().join("w").exists());
+        assert_eq!(fs::read_to_string(dir.path().join("work/work-critically.md")).unwrap(), "body");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("r/review.md")).unwrap(),
+            "{{snippet:work/work-critically}} and {{snippet:work-critically}} and {{.mx/info.md}}"
+        );
+        let history = history_in(dir.path());
+        assert_eq!(history.read_revision("work/work-critically", 1).unwrap(), "body");
+        assert_eq!(
+            history.read_revision("r/review", 1).unwrap(),
+            "{{snippet:w/wc}} and {{snippet:wc}} and {{.mx/info.md}}"
+        );
+    }
+
+    #[test]
+    fn execute_keeps_key_references_when_key_is_unchanged() {
+        let (catalog, dir) = catalog_with_files(&[
+            ("w/wc", "body"),
+            ("r/review", "{{snippet:wc}} {{snippet:w/wc}}"),
+        ]);
+
+        execute("w/wc", "work/wc", &catalog, &history_in(dir.path()), dir.path())
+            .expect("mv should succeed");
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("r/review.md")).unwrap(),
+            "{{snippet:wc}} {{snippet:work/wc}}"
+        );
+    }
+
+    #[test]
+    fn execute_rewrites_extends_in_front_matter_and_sidecars() {
+        let (catalog, dir) = catalog_with_files(&[
+            ("base/review", "{{#block checklist}}x{{/block}}"),
+            ("r/strict", "---\nextends: base/review\n---\n"),
+            ("r/light", "light"),
+        ]);
+        fs::write(dir.path().join("r/light.meta.yaml"), "extends: review\n").unwrap();
+
+        let outcome = execute(
+            "base/review",
+            "team/review-base",
+            &catalog,
+            &history_in(dir.path()),
+            dir.path(),
+        )
+        .expect("mv should succeed");
+
+        assert_eq!(outcome.updated_snippets, vec!["r/strict", "r/light"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("r/strict.md")).unwrap(),
+            "---\nextends: team/review-base\n---\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("r/light.meta.yaml")).unwrap(),
+            "extends: review-base\n"
+        );
+    }
+
+    #[test]
+    fn execute_refuses_to_overwrite_files_the_catalog_does_not_list() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body")]);
+        fs::write(dir.path().join("w/wc.meta.yaml"), "title: T\n").unwrap();
+        fs::create_dir_all(dir.path().join("work")).unwrap();
+        fs::write(dir.path().join("work/wc.meta.yaml"), "title: Kept\n").unwrap();
+
+        let err = execute("wc", "work/wc", &catalog, &history_in(dir.path()), dir.path())
+            .expect_err("existing sidecar");
+        assert!(err.to_string().contains("already exists"));
+        assert!(dir.path().join("w/wc.md").exists());
+        assert!(!dir.path().join("work/wc.md").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("work/wc.meta.yaml")).unwrap(),
+            "title: Kept\n"
+        );
+    }
+
+    #[test]
+    fn execute_refuses_colliding_target() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body"), ("r/review", "review")]);
+
+        let err = execute("wc", "w/review", &catalog, &history_in(dir.path()), dir.path())
+            .expect_err("collision");
+        assert!(matches!(err, AppError::ConfigError(ConfigError::DuplicateSnippet(_))));
+        assert!(dir.path().join("w/wc.md").exists());
+    }
+
+    #[test]
+    fn execute_changes_nothing_when_a_snippet_cannot_be_read() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body"), ("r/review", "review")]);
+        fs::remove_file(dir.path().join("r/review.md")).unwrap();
+        fs::create_dir(dir.path().join("r/review.md")).unwrap();
+
+        execute("wc", "work/wc", &catalog, &history_in(dir.path()), dir.path())
+            .expect_err("unreadable snippet");
+        assert!(dir.path().join("w/wc.md").exists());
+        assert!(!dir.path().join("work").exists());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::{AppError, ConfigError};
+use crate::snippets::{
+    candidate_key, conflicting_snippets, normalize_query, render_frontmatter, SnippetCatalog,
+    SnippetFrontmatter, SnippetHistory,
+};
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum SnippetSource {
+    Scaffold,
+    Clipboard,
+    Text(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewSnippetOptions {
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOutcome {
+    pub snippet: String,
+    pub relative_path: String,
+    pub absolute_path: PathBuf,
+}
+
+pub fn execute(
+    name: &str,
+    body: Option<&str>,
+    options: &NewSnippetOptions,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<NewOutcome, AppError> {
+    let relative_path = normalize_query(name)?.to_string();
+    let existing = catalog.enumerate_all_snippets()?;
+    let conflicts = conflicting_snippets(&existing, &relative_path);
+    if !conflicts.is_empty() {
+        let paths = conflicts.iter().map(|entry| entry.relative_path.as_str()).collect::<Vec<_>>();
+        return Err(AppError::ConfigError(ConfigError::DuplicateSnippet(format!(
+            "'{relative_path}' would collide with existing snippets: {}",
+            paths.join(", ")
+        ))));
+    }
+
+    let key = candidate_key(&relative_path);
+    let metadata = SnippetFrontmatter {
+        title: Some(options.title.clone().unwrap_or_else(|| key.clone())),
+        tags: (!options.tags.is_empty()).then(|| options.tags.clone()),
+        ..SnippetFrontmatter::default()
+    };
+    let frontmatter = render_frontmatter(&metadata)
+        .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+
+    let absolute_path = commands_root.join(format!("{relative_path}.md"));
+    if let Some(parent) = absolute_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().write(true).create_new(true).open(&absolute_path)?;
+    file.write_all(frontmatter.as_bytes())?;
+    file.write_all(body.unwrap_or_default().as_bytes())?;
+    drop(file);
+    history.observe(&relative_path, &absolute_path, "new")?;
+
+    Ok(NewOutcome { snippet: key, relative_path, absolute_path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in, parse_frontmatter_metadata};
+
+    #[test]
+    fn execute_writes_snippet_with_generated_frontmatter() {
+        let (catalog, dir) = catalog_with_files(&[]);
+        let options = NewSnippetOptions {
+            title: Some("Security pass".to_string()),
+            tags: vec!["review".to_string()],
+        };
+
+        let outcome = execute(
+            "review/security-pass",
+            Some("Check inputs\n"),
+            &options,
+            &catalog,
+            &history_in(dir.path()),
+            dir.path(),
+        )
+        .expect("new snippet should be created");
+
+        assert_eq!(outcome.snippet, "security-pass");
+        assert_eq!(outcome.relative_path, "review/security-pass");
+        let content = fs::read_to_string(dir.path().join("review/security-pass.md")).unwrap();
+        let metadata = parse_frontmatter_metadata(&content).unwrap().unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Security pass"));
+        assert_eq!(metadata.tags, Some(vec!["review".to_string()]));
+        assert!(content.ends_with("---\nCheck inputs\n"));
+        let revisions = history_in(dir.path()).revisions("review/security-pass").unwrap();
+        assert_eq!(revisions[0].reason, "new");
+    }
+
+    #[test]
+    fn execute_scaffolds_title_from_key() {
+        let (catalog, dir) = catalog_with_files(&[]);
+
+        execute(
+            "w/wc.md",
+            None,
+            &NewSnippetOptions::default(),
+            &catalog,
+            &history_in(dir.path()),
+            dir.path(),
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(dir.path().join("w/wc.md")).unwrap();
+        assert_eq!(content, "---\ntitle: wc\n---\n");
+    }
+
+    #[test]
+    fn execute_refuses_names_that_collide_with_existing_keys() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "")]);
+
+        let err = execute(
+            "other/wc",
+            None,
+            &NewSnippetOptions::default(),
+            &catalog,
+            &history_in(dir.path()),
+            dir.path(),
+        )
+        .expect_err("colliding key should be rejected");
+        assert!(matches!(err, AppError::ConfigError(ConfigError::DuplicateSnippet(_))));
+        assert!(!dir.path().join("other/wc.md").exists());
+    }
+
+    #[test]
+    fn execute_rejects_traversal_names() {
+        let (catalog, dir) = catalog_with_files(&[]);
+
+        let result = execute(
+            "../escape",
+            None,
+            &NewSnippetOptions::default(),
+            &catalog,
+            &history_in(dir.path()),
+            dir.path(),
+        );
+        assert!(result.is_err());
+    }
+}
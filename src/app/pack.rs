@@ -0,0 +1,415 @@
+use crate::error::{AppError, ConfigError, NotFoundError};
+use crate::snippets::{
+    candidate_key, conflicting_snippets, normalize_query, read_pack, read_snippet_metadata,
+    sha256_hex, sidecar_path, variant_files, variant_path, write_pack, PackEntry, PackFile,
+    PackManifest, PackVariant, SnippetCatalog, SnippetEntry, SnippetHistory, SnippetPack,
+    PACK_FORMAT,
+};
+use crate::timestamp::unix_now;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct PackExportOptions {
+    /// Defaults to a short digest of the entry checksums, so identical packs share a version.
+    pub version: Option<String>,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    Skip,
+    Rename,
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PackImportOptions {
+    pub prefix: Option<String>,
+    /// How to handle entries that collide with the catalog; `None` refuses the whole import.
+    pub on_conflict: Option<ConflictStrategy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportAction {
+    Added,
+    Skipped,
+    Renamed,
+    Overwritten,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedEntry {
+    /// Path recorded in the pack manifest.
+    pub pack_path: String,
+    /// Path under the commands root the entry was written to (or would have been, if skipped).
+    pub relative_path: String,
+    pub action: ImportAction,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackImportOutcome {
+    pub manifest: PackManifest,
+    pub entries: Vec<ImportedEntry>,
+}
+
+/// Writes every snippet under the directory `selector`, or tagged with `selector` when no such
+/// directory exists, into a pack at `output`.
+pub fn export(
+    selector: &str,
+    output: &Path,
+    options: &PackExportOptions,
+    catalog: &dyn SnippetCatalog,
+    commands_root: &Path,
+) -> Result<PackManifest, AppError> {
+    let selected = select_snippets(selector, catalog, commands_root)?;
+    if selected.is_empty() {
+        return Err(AppError::NotFound(NotFoundError::Snippet(format!(
+            "no snippets under directory or tagged '{selector}'"
+        ))));
+    }
+
+    let mut files = Vec::with_capacity(selected.len());
+    let mut entries = Vec::with_capacity(selected.len());
+    for snippet in selected {
+        let content = fs::read_to_string(&snippet.absolute_path)?;
+        let metadata = read_optional(&sidecar_path(&snippet.absolute_path))?;
+        let mut variants = Vec::new();
+        for (language, path) in variant_files(&snippet.absolute_path) {
+            variants.push((language, fs::read_to_string(path)?));
+        }
+        entries.push(PackEntry {
+            path: snippet.relative_path.clone(),
+            sha256: sha256_hex(content.as_bytes()),
+            metadata_sha256: metadata.as_deref().map(|metadata| sha256_hex(metadata.as_bytes())),
+            variants: variants
+                .iter()
+                .map(|(language, content)| PackVariant {
+                    language: language.clone(),
+                    sha256: sha256_hex(content.as_bytes()),
+                })
+                .collect(),
+        });
+        files.push(PackFile { relative_path: snippet.relative_path, content, metadata, variants });
+    }
+
+    let version = options.version.clone().unwrap_or_else(|| {
+        let checksums: String = entries.iter().map(|entry| entry.sha256.as_str()).collect();
+        sha256_hex(checksums.as_bytes())[..12].to_string()
+    });
+    let manifest = PackManifest {
+        format: PACK_FORMAT,
+        version,
+        author: options.author.clone(),
+        created_at: unix_now(),
+        entries,
+    };
+
+    let pack = SnippetPack { manifest, files };
+    write_pack(BufWriter::new(File::create(output)?), &pack)?;
+    Ok(pack.manifest)
+}
+
+/// Adds the snippets of the pack at `input` to the catalog. Conflicts are resolved for every
+/// entry before anything is written, so a refused import leaves the commands root untouched.
+pub fn import(
+    input: &Path,
+    options: &PackImportOptions,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+) -> Result<PackImportOutcome, AppError> {
+    let pack = read_pack(File::open(input)?)?;
+    let prefix = options.prefix.as_deref().unwrap_or_default().trim_matches('/');
+
+    let mut known = catalog.enumerate_all_snippets()?;
+    let mut planned = Vec::with_capacity(pack.files.len());
+    let mut unresolved = Vec::new();
+
+    for file in &pack.files {
+        let target = if prefix.is_empty() {
+            file.relative_path.clone()
+        } else {
+            normalize_query(&format!("{prefix}/{}", file.relative_path))?.to_string()
+        };
+
+        let conflicting = conflicting_snippets(&known, &target);
+        let replaceable = conflicting.iter().all(|entry| {
+            entry.relative_path == target && entry.absolute_path.starts_with(commands_root)
+        });
+        let conflicts: Vec<String> =
+            conflicting.into_iter().map(|entry| entry.relative_path.clone()).collect();
+        let (relative_path, action) = match (conflicts.is_empty(), options.on_conflict) {
+            (true, _) => (target, ImportAction::Added),
+            (false, Some(ConflictStrategy::Skip)) => (target, ImportAction::Skipped),
+            (false, Some(ConflictStrategy::Rename)) => {
+                (available_path(&known, &target), ImportAction::Renamed)
+            }
+            (false, Some(ConflictStrategy::Overwrite)) if replaceable => {
+                (target, ImportAction::Overwritten)
+            }
+            (false, _) => {
+                unresolved.push(format!("{target} (conflicts with {})", conflicts.join(", ")));
+                continue;
+            }
+        };
+
+        if action != ImportAction::Skipped {
+            known.retain(|entry| entry.relative_path != relative_path);
+            known.push(SnippetEntry {
+                key: candidate_key(&relative_path),
+                relative_path: relative_path.clone(),
+                absolute_path: commands_root.join(format!("{relative_path}.md")),
+            });
+        }
+        planned.push((
+            file,
+            ImportedEntry { pack_path: file.relative_path.clone(), relative_path, action },
+        ));
+    }
+
+    if !unresolved.is_empty() {
+        return Err(AppError::ConfigError(ConfigError::DuplicateSnippet(format!(
+            "pack entries collide with existing snippets: {}",
+            unresolved.join("; ")
+        ))));
+    }
+
+    let mut entries = Vec::with_capacity(planned.len());
+    for (file, entry) in planned {
+        if entry.action != ImportAction::Skipped {
+            let path = commands_root.join(format!("{}.md", entry.relative_path));
+            if path.exists() {
+                history.observe(&entry.relative_path, &path, "observed")?;
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &file.content)?;
+            // An overwritten snippet keeps no metadata or variants of its own.
+            let sidecar = sidecar_path(&path);
+            match &file.metadata {
+                Some(metadata) => fs::write(&sidecar, metadata)?,
+                None if sidecar.is_file() => fs::remove_file(&sidecar)?,
+                None => {}
+            }
+            for (_, stale) in variant_files(&path) {
+                fs::remove_file(stale)?;
+            }
+            for (language, content) in &file.variants {
+                fs::write(variant_path(&path, language), content)?;
+            }
+            history.observe(&entry.relative_path, &path, "import")?;
+        }
+        entries.push(entry);
+    }
+
+    Ok(PackImportOutcome { manifest: pack.manifest, entries })
+}
+
+fn select_snippets(
+    selector: &str,
+    catalog: &dyn SnippetCatalog,
+    commands_root: &Path,
+) -> Result<Vec<SnippetEntry>, AppError> {
+    // Hidden snippets travel with the pack: the others may include them.
+    let snippets = catalog.enumerate_all_snippets()?;
+    let directory = normalize_query(selector)?.to_string();
+    if commands_root.join(&directory).is_dir() {
+        let prefix = format!("{directory}/");
+        return Ok(snippets
+            .into_iter()
+            .filter(|snippet| snippet.relative_path.starts_with(&prefix))
+            .collect());
+    }
+
+    let mut tagged = Vec::new();
+    for snippet in snippets {
+        let content = fs::read_to_string(&snippet.absolute_path)?;
+        let tags = read_snippet_metadata(&snippet.absolute_path, &content)
+            .ok()
+            .flatten()
+            .and_then(|frontmatter| frontmatter.tags)
+            .unwrap_or_default();
+        if tags.iter().any(|tag| tag == selector) {
+            tagged.push(snippet);
+        }
+    }
+    Ok(tagged)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, AppError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// First `<target>-<n>` whose path and key are both free.
+fn available_path(known: &[SnippetEntry], target: &str) -> String {
+    (2..)
+        .map(|n| format!("{target}-{n}"))
+        .find(|candidate| conflicting_snippets(known, candidate).is_empty())
+        .unwrap_or_else(|| target.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{history_in, InMemoryCatalog};
+    use tempfile::TempDir;
+
+    fn exported_pack(dir: &TempDir) -> std::path::PathBuf {
+        let source = dir.path().join("source");
+        let catalog = InMemoryCatalog::with_files(
+            &source,
+            &[
+                ("review/security", "Check inputs\n"),
+                ("review/wc", "Plan critically\n"),
+                ("w/other", "unrelated\n"),
+            ],
+        );
+        let output = dir.path().join("pack.tar");
+        export("review", &output, &PackExportOptions::default(), &catalog, &source).unwrap();
+        output
+    }
+
+    #[test]
+    fn export_selects_by_directory_then_by_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        let catalog = InMemoryCatalog::with_files(
+            &source,
+            &[("review/security", "a\n"), ("w/wc", "---\ntags: [team]\n---\nb\n")],
+        );
+        let options = PackExportOptions {
+            version: Some("2.0.0".to_string()),
+            author: Some("platform".to_string()),
+        };
+
+        let by_dir = export("review", &dir.path().join("a.tar"), &options, &catalog, &source)
+            .expect("directory export");
+        assert_eq!(by_dir.version, "2.0.0");
+        assert_eq!(by_dir.entries.len(), 1);
+        assert_eq!(by_dir.entries[0].path, "review/security");
+
+        let by_tag = export("team", &dir.path().join("b.tar"), &options, &catalog, &source)
+            .expect("tag export");
+        assert_eq!(by_tag.entries[0].path, "w/wc");
+
+        let missing = export("nothing", &dir.path().join("c.tar"), &options, &catalog, &source);
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn import_adds_entries_under_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = exported_pack(&dir);
+        let target = dir.path().join("target");
+        let catalog = InMemoryCatalog::new(Vec::new());
+        let options =
+            PackImportOptions { prefix: Some("team/".to_string()), ..PackImportOptions::default() };
+
+        let outcome = import(&pack, &options, &catalog, &history_in(dir.path()), &target).unwrap();
+
+        let paths: Vec<&str> =
+            outcome.entries.iter().map(|entry| entry.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["team/review/security", "team/review/wc"]);
+        assert!(outcome.entries.iter().all(|entry| entry.action == ImportAction::Added));
+        assert_eq!(
+            fs::read_to_string(target.join("team/review/security.md")).unwrap(),
+            "Check inputs\n"
+        );
+    }
+
+    #[test]
+    fn import_refuses_conflicts_without_strategy() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = exported_pack(&dir);
+        let target = dir.path().join("target");
+        let catalog = InMemoryCatalog::with_files(&target, &[("mine/wc", "mine\n")]);
+
+        let err = import(
+            &pack,
+            &PackImportOptions::default(),
+            &catalog,
+            &history_in(dir.path()),
+            &target,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("review/wc (conflicts with mine/wc)"));
+        assert!(!target.join("review").exists());
+    }
+
+    #[test]
+    fn import_applies_conflict_strategies() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = exported_pack(&dir);
+        let target = dir.path().join("target");
+        let catalog = InMemoryCatalog::with_files(
+            &target,
+            &[("mine/wc", "mine\n"), ("review/security", "old\n")],
+        );
+        let history = history_in(dir.path());
+
+        let rename = PackImportOptions {
+            on_conflict: Some(ConflictStrategy::Rename),
+            ..PackImportOptions::default()
+        };
+        let outcome = import(&pack, &rename, &catalog, &history, &target).unwrap();
+        let paths: Vec<&str> =
+            outcome.entries.iter().map(|entry| entry.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["review/security-2", "review/wc-2"]);
+        assert_eq!(fs::read_to_string(target.join("review/security.md")).unwrap(), "old\n");
+
+        let overwrite = PackImportOptions {
+            on_conflict: Some(ConflictStrategy::Overwrite),
+            ..PackImportOptions::default()
+        };
+        let err = import(&pack, &overwrite, &catalog, &history, &target).unwrap_err();
+        assert!(err.to_string().contains("review/wc (conflicts with mine/wc)"));
+
+        let catalog = InMemoryCatalog::with_files(&target, &[("review/security", "old\n")]);
+        let outcome = import(&pack, &overwrite, &catalog, &history, &target).unwrap();
+        assert_eq!(outcome.entries[0].action, ImportAction::Overwritten);
+        assert_eq!(
+            fs::read_to_string(target.join("review/security.md")).unwrap(),
+            "Check inputs\n"
+        );
+        assert_eq!(history.read_revision("review/security", 1).unwrap(), "old\n");
+    }
+
+    #[test]
+    fn sidecar_metadata_and_variants_travel_with_their_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        let catalog = InMemoryCatalog::with_files(&source, &[("review/wc", "Plan critically\n")]);
+        fs::write(source.join("review/wc.meta.yaml"), "tags: [team]\n").unwrap();
+        fs::write(source.join("review/wc.ja.md"), "批判的に計画する\n").unwrap();
+        let output = dir.path().join("pack.tar");
+
+        let manifest =
+            export("team", &output, &PackExportOptions::default(), &catalog, &source).unwrap();
+        assert_eq!(
+            manifest.entries[0].metadata_sha256.as_deref(),
+            Some(sha256_hex(b"tags: [team]\n").as_str())
+        );
+        assert_eq!(manifest.entries[0].variants[0].language, "ja");
+
+        let target = dir.path().join("target");
+        let catalog = InMemoryCatalog::new(Vec::new());
+        import(&output, &PackImportOptions::default(), &catalog, &history_in(dir.path()), &target)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("review/wc.meta.yaml")).unwrap(),
+            "tags: [team]\n"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("review/wc.ja.md")).unwrap(),
+            "批判的に計画する\n"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::error::{AppError, NotFoundError};
+use crate::snippets::{
+    parse_pin_slot, read_snippet_metadata, validate_slot, Pin, PinStore, SnippetCatalog,
+    SnippetEntry,
+};
+use serde::Serialize;
+use std::fs;
+
+#[derive(Debug, Clone)]
+pub struct PinOutcome {
+    pub slot: u8,
+    pub snippet: String,
+    pub relative_path: String,
+    /// The snippet that held the slot before, if any.
+    pub replaced: Option<Pin>,
+}
+
+/// A slot for `mx list --pinned`. `relative_path` is where the snippet was found, or the
+/// pinned path when it no longer resolves.
+#[derive(Debug, Clone, Serialize)]
+pub struct PinnedEntry {
+    pub slot: u8,
+    pub snippet: String,
+    pub relative_path: String,
+    pub title: Option<String>,
+    pub missing: bool,
+}
+
+pub fn pin(
+    slot: u8,
+    query: &str,
+    catalog: &dyn SnippetCatalog,
+    store: &PinStore,
+) -> Result<PinOutcome, AppError> {
+    validate_slot(slot)?;
+    let entry = catalog.resolve_snippet(query)?;
+    let alias = fs::read_to_string(&entry.absolute_path)
+        .ok()
+        .and_then(|content| read_snippet_metadata(&entry.absolute_path, &content).ok().flatten())
+        .and_then(|metadata| metadata.aliases)
+        .and_then(|aliases| aliases.into_iter().next());
+    let pin = Pin { path: entry.relative_path.clone(), key: entry.key.clone(), alias };
+    let replaced = store.set(slot, pin)?;
+
+    Ok(PinOutcome { slot, snippet: entry.key, relative_path: entry.relative_path, replaced })
+}
+
+pub fn unpin(slot: u8, store: &PinStore) -> Result<Pin, AppError> {
+    store.remove(slot)?.ok_or_else(|| empty_slot(slot))
+}
+
+/// Resolves `query` when it names a pin slot (`1` or `:1`). A bare number without a pin is
+/// left to normal snippet resolution, so `Ok(None)` is returned for it.
+pub fn resolve_query(
+    query: &str,
+    catalog: &dyn SnippetCatalog,
+    store: &PinStore,
+) -> Result<Option<SnippetEntry>, AppError> {
+    let Some(slot) = parse_pin_slot(query) else {
+        return Ok(None);
+    };
+    match store.get(slot)? {
+        Some(pin) => resolve_pin(&pin, catalog, store).map(Some),
+        None if query.trim().starts_with(':') => Err(empty_slot(slot)),
+        None => Ok(None),
+    }
+}
+
+pub fn list(catalog: &dyn SnippetCatalog, store: &PinStore) -> Result<Vec<PinnedEntry>, AppError> {
+    let mut entries = Vec::new();
+    for (slot, pin) in store.pins()? {
+        let entry = match resolve_pin(&pin, catalog, store) {
+            Ok(entry) => {
+                let title = fs::read_to_string(&entry.absolute_path).ok().and_then(|content| {
+                    read_snippet_metadata(&entry.absolute_path, &content)
+                        .ok()
+                        .flatten()
+                        .and_then(|metadata| metadata.title)
+                });
+                PinnedEntry {
+                    slot,
+                    snippet: entry.key,
+                    relative_path: entry.relative_path,
+                    title,
+                    missing: false,
+                }
+            }
+            Err(_) => PinnedEntry {
+                slot,
+                snippet: pin.key,
+                relative_path: pin.path,
+                title: None,
+                missing: true,
+            },
+        };
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Finds a pinned snippet at its pinned path, or else by its alias when it was moved outside
+/// mx and exactly one snippet still carries that alias. A pin found elsewhere is updated to the
+/// new path. Keys are not unique enough to follow, so a pin without such an alias is missing.
+fn resolve_pin(
+    pin: &Pin,
+    catalog: &dyn SnippetCatalog,
+    store: &PinStore,
+) -> Result<SnippetEntry, AppError> {
+    let snippets = catalog.enumerate_snippets()?;
+    if let Some(entry) = snippets.iter().find(|entry| entry.relative_path == pin.path) {
+        return Ok(entry.clone());
+    }
+
+    let by_alias = pin.alias.as_deref().and_then(|alias| {
+        let mut matches = snippets.iter().filter(|entry| has_alias(entry, alias));
+        match (matches.next(), matches.next()) {
+            (Some(entry), None) => Some(entry.clone()),
+            _ => None,
+        }
+    });
+    let entry = by_alias.ok_or_else(|| {
+        AppError::NotFound(NotFoundError::Snippet(format!(
+            "Pinned snippet '{}' no longer exists",
+            pin.path
+        )))
+    })?;
+
+    // The pin still works without the update; it just keeps searching next time.
+    let _ = store.rename(&pin.path, &entry.relative_path, &entry.key);
+    Ok(entry)
+}
+
+fn has_alias(entry: &SnippetEntry, alias: &str) -> bool {
+    fs::read_to_string(&entry.absolute_path)
+        .ok()
+        .and_then(|content| read_snippet_metadata(&entry.absolute_path, &content).ok().flatten())
+        .and_then(|metadata| metadata.aliases)
+        .is_some_and(|aliases| aliases.iter().any(|candidate| candidate == alias))
+}
+
+fn empty_slot(slot: u8) -> AppError {
+    AppError::NotFound(NotFoundError::Snippet(format!("No snippet pinned to slot {slot}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::catalog_with_files;
+
+    #[test]
+    fn pinned_slots_resolve_with_or_without_colon() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body")]);
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+
+        let outcome = pin(1, "wc", &catalog, &store).unwrap();
+        assert_eq!(outcome.relative_path, "w/wc");
+
+        assert_eq!(resolve_query("1", &catalog, &store).unwrap().unwrap().relative_path, "w/wc");
+        assert_eq!(resolve_query(":1", &catalog, &store).unwrap().unwrap().relative_path, "w/wc");
+        assert!(resolve_query("2", &catalog, &store).unwrap().is_none());
+        assert!(resolve_query(":2", &catalog, &store).is_err());
+        assert!(resolve_query("wc", &catalog, &store).unwrap().is_none());
+    }
+
+    #[test]
+    fn pins_follow_a_snippet_moved_outside_mx_by_alias() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "---\naliases: [plan]\n---\nbody")]);
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+        pin(1, "wc", &catalog, &store).unwrap();
+
+        let (moved, _moved_dir) =
+            catalog_with_files(&[("review/critical", "---\naliases: [plan]\n---\nbody")]);
+        let entry = resolve_query("1", &moved, &store).unwrap().unwrap();
+
+        assert_eq!(entry.relative_path, "review/critical");
+        assert_eq!(store.get(1).unwrap().unwrap().path, "review/critical");
+    }
+
+    #[test]
+    fn pins_do_not_rebind_to_another_snippet_with_the_same_key() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body")]);
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+        pin(1, "wc", &catalog, &store).unwrap();
+
+        let (other, _other_dir) = catalog_with_files(&[("team/wc", "someone else's")]);
+        let err = resolve_query("1", &other, &store).unwrap_err();
+
+        assert!(err.to_string().contains("Pinned snippet 'w/wc' no longer exists"));
+        assert_eq!(store.get(1).unwrap().unwrap().path, "w/wc");
+    }
+
+    #[test]
+    fn list_marks_pins_whose_snippet_is_gone() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "---\ntitle: Work\n---\nbody")]);
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+        pin(1, "wc", &catalog, &store).unwrap();
+        store.set(2, Pin { path: "gone/old".into(), key: "old".into(), alias: None }).unwrap();
+
+        let entries = list(&catalog, &store).unwrap();
+
+        assert_eq!(entries[0].title.as_deref(), Some("Work"));
+        assert!(!entries[0].missing);
+        assert_eq!(entries[1].relative_path, "gone/old");
+        assert!(entries[1].missing);
+        assert!(unpin(3, &store).is_err());
+        assert_eq!(unpin(2, &store).unwrap().path, "gone/old");
+    }
+}
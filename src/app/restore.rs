@@ -0,0 +1,70 @@
+use crate::error::AppError;
+use crate::snippets::{SnippetCatalog, SnippetHistory};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct RestoreOutcome {
+    pub relative_path: String,
+    pub revision: u64,
+    pub absolute_path: PathBuf,
+}
+
+/// Writes a recorded revision back to the snippet file. The contents being replaced are
+/// snapshotted first so the restore itself can be undone.
+pub fn execute(
+    query: &str,
+    revision: u64,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+) -> Result<RestoreOutcome, AppError> {
+    let entry = catalog.resolve_snippet(query)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "observed")?;
+
+    let content = history.read_revision(&entry.relative_path, revision)?;
+    fs::write(&entry.absolute_path, content)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "restore")?;
+
+    Ok(RestoreOutcome {
+        relative_path: entry.relative_path,
+        revision,
+        absolute_path: entry.absolute_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn execute_restores_revision_and_keeps_replaced_contents() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "tuned prompt")]);
+        let path = dir.path().join("w/wc.md");
+        let history = history_in(dir.path());
+        history.observe("w/wc", &path, "observed").unwrap();
+
+        fs::write(&path, "accidental overwrite").unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+
+        execute("wc", 1, &catalog, &history).expect("restore should succeed");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "tuned prompt");
+        let revisions = history.revisions("w/wc").unwrap();
+        assert_eq!(history.read_revision("w/wc", 2).unwrap(), "accidental overwrite");
+        assert_eq!(revisions.last().unwrap().reason, "restore");
+    }
+
+    #[test]
+    fn execute_fails_for_unknown_revision() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body")]);
+        let path = dir.path().join("w/wc.md");
+        let history = history_in(dir.path());
+
+        let err = execute("wc", 9, &catalog, &history).unwrap_err();
+        assert!(err.to_string().contains("No revision 9 recorded for w/wc"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "body");
+    }
+}
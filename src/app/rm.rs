@@ -0,0 +1,77 @@
+use crate::error::AppError;
+use crate::project_fs::{create_timestamped_dir, prune_empty_dirs};
+use crate::snippets::{SnippetCatalog, SnippetHistory};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct RemoveOutcome {
+    pub snippet: String,
+    pub relative_path: String,
+    pub trash_path: PathBuf,
+}
+
+/// Moves the resolved snippet into a new batch under `trash_root` instead of deleting it.
+/// Its history is kept so a snippet recreated at the same path can still be rolled back.
+pub fn execute(
+    query: &str,
+    catalog: &dyn SnippetCatalog,
+    history: &SnippetHistory,
+    commands_root: &Path,
+    trash_root: &Path,
+) -> Result<RemoveOutcome, AppError> {
+    let entry = catalog.resolve_snippet(query)?;
+    super::ensure_local(&entry, commands_root)?;
+    history.observe(&entry.relative_path, &entry.absolute_path, "observed")?;
+    let batch = create_timestamped_dir(trash_root)?;
+    let trash_path = batch.join(format!("{}.md", entry.relative_path));
+
+    super::move_snippet_file(&entry.absolute_path, &trash_path)?;
+    if let Some(parent) = entry.absolute_path.parent() {
+        prune_empty_dirs(parent, commands_root);
+    }
+
+    Ok(RemoveOutcome { snippet: entry.key, relative_path: entry.relative_path, trash_path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, history_in};
+    use std::fs;
+
+    #[test]
+    fn execute_moves_snippet_into_trash_batch() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "keep me")]);
+        let snippet_path = dir.path().join("w/wc.md");
+        let history = history_in(dir.path());
+
+        let outcome = execute("wc", &catalog, &history, dir.path(), &dir.path().join("trash"))
+            .expect("rm should succeed");
+
+        assert!(!snippet_path.exists());
+        assert!(!dir.path().join("w").exists());
+        assert!(outcome.trash_path.starts_with(dir.path().join("trash")));
+        assert!(outcome.trash_path.ends_with("w/wc.md"));
+        assert_eq!(fs::read_to_string(outcome.trash_path).unwrap(), "keep me");
+        assert_eq!(history.read_revision("w/wc", 1).unwrap(), "keep me");
+    }
+
+    #[test]
+    fn execute_refuses_snippets_from_synced_sources() {
+        let (catalog, dir) = catalog_with_files(&[("team/wc", "shared")]);
+        let mounted = dir.path().join("team/wc.md");
+        let history = history_in(dir.path());
+
+        let err = execute(
+            "wc",
+            &catalog,
+            &history,
+            &dir.path().join("commands"),
+            &dir.path().join("trash"),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("'team/wc' comes from a synced source"));
+        assert!(mounted.exists());
+    }
+}
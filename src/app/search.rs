@@ -0,0 +1,292 @@
+use crate::error::{AppError, ConfigError};
+use crate::snippets::{
+    read_snippet_metadata, strip_frontmatter, SnippetCatalog, SnippetFrontmatter, UsageStats,
+};
+use regex::{Regex, RegexBuilder};
+use std::fs;
+use std::path::PathBuf;
+
+const TITLE_SCORE: u32 = 10;
+const DESCRIPTION_SCORE: u32 = 5;
+const BODY_SCORE: u32 = 1;
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub ignore_case: bool,
+    pub context_lines: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Title,
+    Description,
+    Body,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    pub field: SearchField,
+    /// 1-based line number within the snippet file; only set for body matches.
+    pub line_number: Option<usize>,
+    pub line: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub snippet: String,
+    pub relative_path: String,
+    pub score: u32,
+    pub matches: Vec<SearchMatch>,
+}
+
+pub fn execute(
+    query: &str,
+    options: &SearchOptions,
+    catalog: &dyn SnippetCatalog,
+    roots: &[PathBuf],
+    usage: &UsageStats,
+) -> Result<Vec<SearchHit>, AppError> {
+    let pattern = build_pattern(query, options)?;
+    let canonical_roots: Vec<PathBuf> =
+        roots.iter().filter_map(|root| root.canonicalize().ok()).collect();
+
+    let mut hits = Vec::new();
+    for snippet in catalog.enumerate_snippets()? {
+        // A dangling link or a file that is not UTF-8 cannot match; it is skipped rather than
+        // failing the whole search.
+        let Ok(canonical_path) = snippet.absolute_path.canonicalize() else {
+            continue;
+        };
+        if !canonical_roots.iter().any(|root| canonical_path.starts_with(root)) {
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(&canonical_path) else {
+            continue;
+        };
+        // Metadata that fails to parse is treated as absent so one broken file
+        // does not hide body matches.
+        let metadata = read_snippet_metadata(&canonical_path, &content).ok().flatten();
+        let matches = search_content(&content, metadata, &pattern, options.context_lines);
+        if matches.is_empty() {
+            continue;
+        }
+
+        let score = matches
+            .iter()
+            .map(|found| match found.field {
+                SearchField::Title => TITLE_SCORE,
+                SearchField::Description => DESCRIPTION_SCORE,
+                SearchField::Body => BODY_SCORE,
+            })
+            .sum();
+
+        hits.push(SearchHit {
+            snippet: snippet.key,
+            relative_path: snippet.relative_path,
+            score,
+            matches,
+        });
+    }
+
+    // Equally good matches are ranked by frecency, so the snippets in use come first.
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| usage.frecency(&b.relative_path).cmp(&usage.frecency(&a.relative_path)))
+            .then_with(|| a.relative_path.cmp(&b.relative_path))
+    });
+    Ok(hits)
+}
+
+fn build_pattern(query: &str, options: &SearchOptions) -> Result<Regex, AppError> {
+    if query.trim().is_empty() {
+        return Err(AppError::ConfigError(ConfigError::Other(
+            "Search query cannot be empty".to_string(),
+        )));
+    }
+
+    let source = if options.regex { query.to_string() } else { regex::escape(query) };
+    RegexBuilder::new(&source).case_insensitive(options.ignore_case).build().map_err(|err| {
+        AppError::ConfigError(ConfigError::Other(format!("Invalid search pattern: {err}")))
+    })
+}
+
+fn search_content(
+    content: &str,
+    metadata: Option<SnippetFrontmatter>,
+    pattern: &Regex,
+    context_lines: usize,
+) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+
+    if let Some(frontmatter) = metadata {
+        for (field, value) in [
+            (SearchField::Title, frontmatter.title),
+            (SearchField::Description, frontmatter.description),
+        ] {
+            if let Some(value) = value.filter(|value| pattern.is_match(value)) {
+                matches.push(SearchMatch {
+                    field,
+                    line_number: None,
+                    line: value,
+                    before: Vec::new(),
+                    after: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let body = strip_frontmatter(content);
+    let body_offset = content[..content.len() - body.len()].matches('\n').count();
+    let lines: Vec<&str> = body.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        if !pattern.is_match(line) {
+            continue;
+        }
+
+        let start = index.saturating_sub(context_lines);
+        let end = (index + 1 + context_lines).min(lines.len());
+        matches.push(SearchMatch {
+            field: SearchField::Body,
+            line_number: Some(body_offset + index + 1),
+            line: line.to_string(),
+            before: lines[start..index].iter().map(|line| line.to_string()).collect(),
+            after: lines[index + 1..end].iter().map(|line| line.to_string()).collect(),
+        });
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{catalog_with_files, InMemoryCatalog, UsageEvent};
+
+    fn search(
+        query: &str,
+        options: &SearchOptions,
+        catalog: &InMemoryCatalog,
+        roots: &[PathBuf],
+    ) -> Result<Vec<SearchHit>, AppError> {
+        execute(query, options, catalog, roots, &UsageStats::default())
+    }
+
+    #[test]
+    fn execute_finds_body_lines_with_context_and_file_line_numbers() {
+        let (catalog, dir) = catalog_with_files(&[(
+            "w/wc",
+            "---\ntitle: Work\n---\nfirst\nplan critically\nlast\n",
+        )]);
+        let options = SearchOptions { context_lines: 1, ..SearchOptions::default() };
+
+        let hits = search("critically", &options, &catalog, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].relative_path, "w/wc");
+        let found = &hits[0].matches[0];
+        assert_eq!(found.field, SearchField::Body);
+        assert_eq!(found.line_number, Some(5));
+        assert_eq!(found.before, vec!["first"]);
+        assert_eq!(found.after, vec!["last"]);
+    }
+
+    #[test]
+    fn execute_ranks_title_matches_above_body_matches() {
+        let (catalog, dir) = catalog_with_files(&[
+            ("a/body", "mentions review once\n"),
+            ("z/titled", "---\ntitle: Security review\n---\nbody\n"),
+        ]);
+
+        let hits =
+            search("review", &SearchOptions::default(), &catalog, &[dir.path().to_path_buf()])
+                .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].relative_path, "z/titled");
+        assert_eq!(hits[0].matches[0].field, SearchField::Title);
+        assert_eq!(hits[1].relative_path, "a/body");
+    }
+
+    #[test]
+    fn execute_supports_case_insensitive_and_regex_modes() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "Plan Critically\nstep 42\n")]);
+
+        let sensitive =
+            search("plan", &SearchOptions::default(), &catalog, &[dir.path().to_path_buf()])
+                .unwrap();
+        assert!(sensitive.is_empty());
+
+        let options = SearchOptions { ignore_case: true, ..SearchOptions::default() };
+        let insensitive = search("plan", &options, &catalog, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(insensitive.len(), 1);
+
+        let options = SearchOptions { regex: true, ..SearchOptions::default() };
+        let regex = search(r"step \d+", &options, &catalog, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(regex[0].matches[0].line, "step 42");
+
+        let literal =
+            search(r"step \d+", &SearchOptions::default(), &catalog, &[dir.path().to_path_buf()]);
+        assert!(literal.unwrap().is_empty());
+    }
+
+    #[test]
+    fn execute_rejects_invalid_regex() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "body\n")]);
+        let options = SearchOptions { regex: true, ..SearchOptions::default() };
+
+        let err = search("(unclosed", &options, &catalog, &[dir.path().to_path_buf()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid search pattern"));
+    }
+
+    #[test]
+    fn execute_skips_entries_outside_commands_root() {
+        let (catalog, dir) = catalog_with_files(&[("w/wc", "secret phrase\n")]);
+        let commands_root = dir.path().join("w/nested");
+        fs::create_dir_all(&commands_root).unwrap();
+
+        let hits = search("secret", &SearchOptions::default(), &catalog, &[commands_root]).unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn execute_breaks_score_ties_by_frecency() {
+        let (catalog, dir) =
+            catalog_with_files(&[("a/first", "review it\n"), ("b/second", "review it\n")]);
+        let usage = UsageStats::from_events(
+            &[UsageEvent {
+                relative_path: "b/second".to_string(),
+                key: "second".to_string(),
+                used_at: 0,
+                workspace: None,
+            }],
+            0,
+        );
+
+        let hits = execute(
+            "review",
+            &SearchOptions::default(),
+            &catalog,
+            &[dir.path().to_path_buf()],
+            &usage,
+        )
+        .unwrap();
+        let paths: Vec<&str> = hits.iter().map(|hit| hit.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["b/second", "a/first"]);
+    }
+
+    #[test]
+    fn execute_skips_files_that_cannot_be_read() {
+        let (catalog, dir) =
+            catalog_with_files(&[("a/binary", "review"), ("w/wc", "review the plan\n")]);
+        fs::write(dir.path().join("a/binary.md"), [0xff, 0xfe]).unwrap();
+
+        let hits =
+            search("review", &SearchOptions::default(), &catalog, &[dir.path().to_path_buf()])
+                .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].relative_path, "w/wc");
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::AppError;
+use crate::snippets::{SnippetCatalog, UsageStats};
+use std::cmp::Reverse;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsEntry {
+    pub snippet: String,
+    pub relative_path: String,
+    pub uses: u64,
+    /// Seconds since the Unix epoch of the last copy.
+    pub last_used: Option<u64>,
+    pub frecency: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsOutcome {
+    /// Snippets copied at least once, highest frecency first.
+    pub used: Vec<StatsEntry>,
+    /// Relative paths of snippets that were never copied, candidates for pruning.
+    pub unused: Vec<String>,
+}
+
+/// Joins the usage log with the current catalog. Usage of snippets that no longer exist is
+/// left out.
+pub fn execute(catalog: &dyn SnippetCatalog, usage: &UsageStats) -> Result<StatsOutcome, AppError> {
+    let mut outcome = StatsOutcome::default();
+    for snippet in catalog.enumerate_snippets()? {
+        let stats = usage.get(&snippet.relative_path);
+        if stats.uses == 0 {
+            outcome.unused.push(snippet.relative_path);
+            continue;
+        }
+        outcome.used.push(StatsEntry {
+            snippet: snippet.key,
+            relative_path: snippet.relative_path,
+            uses: stats.uses,
+            last_used: stats.last_used,
+            frecency: stats.frecency,
+        });
+    }
+
+    outcome.used.sort_by_key(|entry| (Reverse(entry.frecency), Reverse(entry.uses)));
+    outcome.unused.sort();
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippets::{InMemoryCatalog, SnippetEntry, UsageEvent};
+    use std::path::PathBuf;
+
+    fn entry(relative_path: &str) -> SnippetEntry {
+        SnippetEntry {
+            key: relative_path.rsplit('/').next().unwrap().to_string(),
+            relative_path: relative_path.to_string(),
+            absolute_path: PathBuf::from(format!("{relative_path}.md")),
+        }
+    }
+
+    fn event(relative_path: &str, used_at: u64) -> UsageEvent {
+        UsageEvent {
+            relative_path: relative_path.to_string(),
+            key: String::new(),
+            used_at,
+            workspace: None,
+        }
+    }
+
+    #[test]
+    fn execute_ranks_used_snippets_and_lists_unused_ones() {
+        let catalog = InMemoryCatalog::new(vec![entry("a/aa"), entry("w/wc"), entry("z/zz")]);
+        let day = 86_400;
+        let usage = UsageStats::from_events(
+            &[
+                event("a/aa", 0),
+                event("a/aa", day),
+                event("w/wc", 100 * day),
+                event("gone/old", 100 * day),
+            ],
+            100 * day,
+        );
+
+        let outcome = execute(&catalog, &usage).unwrap();
+
+        let ranked: Vec<(&str, u64)> =
+            outcome.used.iter().map(|entry| (entry.relative_path.as_str(), entry.uses)).collect();
+        assert_eq!(ranked, vec![("w/wc", 1), ("a/aa", 2)]);
+        assert_eq!(outcome.unused, vec!["z/zz"]);
+    }
+}
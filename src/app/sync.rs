@@ -0,0 +1,64 @@
+use crate::error::{AppError, NotFoundError};
+use crate::sources::{sync_source, GitSource, SyncStatus};
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct SourceSyncResult {
+    pub name: String,
+    pub status: Result<SyncStatus, AppError>,
+}
+
+/// Syncs every configured source, or only the one called `only`, into `<sources_root>/<name>`.
+/// A failing source does not stop the others from syncing.
+pub fn execute(
+    sources: &[GitSource],
+    only: Option<&str>,
+    sources_root: &Path,
+) -> Result<Vec<SourceSyncResult>, AppError> {
+    let selected: Vec<&GitSource> =
+        sources.iter().filter(|source| only.is_none_or(|name| source.name == name)).collect();
+    if let (Some(name), true) = (only, selected.is_empty()) {
+        return Err(AppError::NotFound(NotFoundError::Snippet(format!(
+            "No source named '{name}' in config.yaml"
+        ))));
+    }
+
+    Ok(selected
+        .into_iter()
+        .map(|source| SourceSyncResult {
+            name: source.name.clone(),
+            status: sync_source(source, &sources_root.join(&source.name)),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, url: &str) -> GitSource {
+        GitSource { name: name.to_string(), url: url.to_string(), reference: None }
+    }
+
+    #[test]
+    fn execute_reports_each_source_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = format!("file://{}", dir.path().join("missing.git").display());
+        let sources = vec![source("a", &missing), source("b", &missing)];
+
+        let results = execute(&sources, None, &dir.path().join("sources")).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.status.is_err()));
+
+        let results = execute(&sources, Some("b"), &dir.path().join("sources")).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "b");
+    }
+
+    #[test]
+    fn execute_rejects_unknown_source_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = execute(&[], Some("team"), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("No source named 'team'"));
+    }
+}
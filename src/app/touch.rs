@@ -1,7 +1,7 @@
-use crate::clipboard::Clipboard;
-use crate::context_files::resolve_validated_context_path;
-use crate::context_files::ContextFileStore;
+use crate::clipboard::ContentSource;
+use crate::context_files::{resolve_validated_context_path_with, AliasRegistry, ContextFileStore};
 use crate::error::AppError;
+use crate::timestamp::{format_utc, unix_now};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -10,20 +10,110 @@ pub struct TouchOutcome {
     pub path: PathBuf,
     pub existed: bool,
     pub overwritten: bool,
+    /// The content was added to the end of the existing file.
+    pub appended: bool,
+}
+
+/// What `mx touch` does with a context file that already exists.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TouchMode {
+    /// Leave it alone.
+    #[default]
+    Create,
+    /// Replace its contents (`--force`).
+    Overwrite,
+    /// Add the content after its contents (`--append`).
+    Append(AppendSeparator),
+}
+
+/// Line written between the existing contents and an appended entry.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AppendSeparator {
+    /// Only make sure the entry starts on a new line.
+    #[default]
+    None,
+    /// A `---` rule.
+    Rule,
+    /// A `## <UTC time>` heading.
+    Timestamp,
+    Custom(String),
+}
+
+impl AppendSeparator {
+    /// Parses `--separator`: `none`, `rule`, `timestamp`, or any other text used as is.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "none" => Self::None,
+            "rule" | "---" => Self::Rule,
+            "timestamp" => Self::Timestamp,
+            custom => Self::Custom(custom.to_string()),
+        }
+    }
+
+    /// The text to append after `existing` so `entry` follows it. An empty file gets the entry
+    /// alone.
+    fn join(&self, existing: &str, entry: &str, now: u64) -> String {
+        if existing.is_empty() {
+            return entry.to_string();
+        }
+        let mut chunk = String::new();
+        if !existing.ends_with('\n') {
+            chunk.push('\n');
+        }
+        let line = match self {
+            Self::None => None,
+            Self::Rule => Some("---".to_string()),
+            Self::Timestamp => Some(format!("## {}", format_utc(now))),
+            Self::Custom(text) => Some(text.clone()),
+        };
+        if let Some(line) = line {
+            chunk.push('\n');
+            chunk.push_str(&line);
+            chunk.push_str("\n\n");
+        }
+        chunk.push_str(entry);
+        chunk
+    }
+}
+
+/// Where `mx touch` reads the new content from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TouchSource {
+    #[default]
+    Clipboard,
+    Stdin,
+    File(PathBuf),
+    /// A shell command whose output is captured.
+    Command(String),
 }
 
 pub fn execute(
     key: &str,
-    force: bool,
+    mode: &TouchMode,
     store: &dyn ContextFileStore,
-    clipboard: &dyn Clipboard,
+    source: &dyn ContentSource,
+    aliases: &AliasRegistry,
 ) -> Result<TouchOutcome, AppError> {
-    let relative_path = resolve_validated_context_path(key)?;
+    let relative_path = resolve_validated_context_path_with(key, aliases)?;
+
+    // Read before preparing the file, so a failed read neither leaves an empty file behind nor
+    // trashes the one `--force` would replace. An existing file left alone needs no content.
+    let content = if *mode != TouchMode::Create || !store.context_file_exists(&relative_path) {
+        Some(source.read()?)
+    } else {
+        None
+    };
 
-    let status = store.prepare_context_file(&relative_path, force)?;
+    let status = store.prepare_context_file(&relative_path, *mode == TouchMode::Overwrite)?;
+    let mut appended = false;
     if status.should_write() {
-        let content = clipboard.paste()?;
+        let content = content.map_or_else(|| source.read(), Ok)?;
         store.write_context_contents(&status.path, &content)?;
+    } else if let (TouchMode::Append(separator), Some(content)) = (mode, content) {
+        let existing = store.read_context_contents(&relative_path)?;
+        let chunk = separator.join(&existing, &content, unix_now());
+        store.append_context_contents(&status.path, &chunk)?;
+        appended = true;
     }
 
     Ok(TouchOutcome {
@@ -31,16 +121,17 @@ pub fn execute(
         path: status.path,
         existed: status.existed,
         overwritten: status.overwritten,
+        appended,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clipboard::InMemoryClipboard;
-    use crate::context_files::resolve_context_path;
+    use crate::clipboard::{FileSource, InMemoryClipboard};
     use crate::context_files::ContextFileStore;
     use crate::context_files::InMemoryContextStore;
+    use crate::context_files::{resolve_context_path_with, resolve_validated_context_path};
 
     #[test]
     fn execute_creates_context_file_with_clipboard_content() {
@@ -48,7 +139,9 @@ mod tests {
         let clipboard = InMemoryClipboard::default();
         clipboard.set_contents("fresh content");
 
-        let outcome = execute("tk", false, &store, &clipboard).expect("touch should succeed");
+        let outcome =
+            execute("tk", &TouchMode::Create, &store, &clipboard, &AliasRegistry::builtin())
+                .expect("touch should succeed");
         assert_eq!(outcome.key, "tk");
         assert!(!outcome.existed);
         assert!(!outcome.overwritten);
@@ -56,7 +149,11 @@ mod tests {
 
         let content = store
             .read_context_contents(
-                &crate::project_fs::SafePath::try_from_path(&resolve_context_path("tk")).unwrap(),
+                &crate::project_fs::SafePath::try_from_path(&resolve_context_path_with(
+                    "tk",
+                    &AliasRegistry::builtin(),
+                ))
+                .unwrap(),
             )
             .unwrap();
         assert_eq!(content, "fresh content");
@@ -67,33 +164,101 @@ mod tests {
         let store = InMemoryContextStore::default();
         let clipboard = InMemoryClipboard::default();
         clipboard.set_contents("initial");
-        execute("tk", false, &store, &clipboard).unwrap();
+        execute("tk", &TouchMode::Create, &store, &clipboard, &AliasRegistry::builtin()).unwrap();
 
         clipboard.set_contents("updated");
-        let skipped = execute("tk", false, &store, &clipboard).unwrap();
+        let skipped =
+            execute("tk", &TouchMode::Create, &store, &clipboard, &AliasRegistry::builtin())
+                .unwrap();
         assert!(skipped.existed);
         assert!(!skipped.overwritten);
         assert_eq!(
             store
                 .read_context_contents(
-                    &crate::project_fs::SafePath::try_from_path(&resolve_context_path("tk"))
-                        .unwrap()
+                    &crate::project_fs::SafePath::try_from_path(&resolve_context_path_with(
+                        "tk",
+                        &AliasRegistry::builtin()
+                    ))
+                    .unwrap()
                 )
                 .unwrap(),
             "initial"
         );
 
-        let forced = execute("tk", true, &store, &clipboard).unwrap();
+        let forced =
+            execute("tk", &TouchMode::Overwrite, &store, &clipboard, &AliasRegistry::builtin())
+                .unwrap();
         assert!(forced.existed);
         assert!(forced.overwritten);
         assert_eq!(
             store
                 .read_context_contents(
-                    &crate::project_fs::SafePath::try_from_path(&resolve_context_path("tk"))
-                        .unwrap()
+                    &crate::project_fs::SafePath::try_from_path(&resolve_context_path_with(
+                        "tk",
+                        &AliasRegistry::builtin()
+                    ))
+                    .unwrap()
                 )
                 .unwrap(),
             "updated"
         );
     }
+
+    #[test]
+    fn execute_appends_with_the_chosen_separator() {
+        let store = InMemoryContextStore::default();
+        let clipboard = InMemoryClipboard::default();
+        let aliases = AliasRegistry::builtin();
+        let append = TouchMode::Append(AppendSeparator::Rule);
+
+        clipboard.set_contents("first error");
+        let created = execute("er", &append, &store, &clipboard, &aliases).unwrap();
+        assert!(!created.existed && !created.appended);
+
+        clipboard.set_contents("second error\n");
+        let appended = execute("er", &append, &store, &clipboard, &aliases).unwrap();
+        assert!(appended.existed && appended.appended && !appended.overwritten);
+
+        let path =
+            crate::project_fs::SafePath::try_from_path(&resolve_context_path_with("er", &aliases))
+                .unwrap();
+        assert_eq!(
+            store.read_context_contents(&path).unwrap(),
+            "first error\n\n---\n\nsecond error\n"
+        );
+    }
+
+    #[test]
+    fn execute_leaves_no_trace_when_the_source_cannot_be_read() {
+        let store = InMemoryContextStore::default();
+        let aliases = AliasRegistry::builtin();
+        let missing = FileSource::new(PathBuf::from("/nonexistent/mx/input.txt"));
+
+        assert!(execute("tk", &TouchMode::Create, &store, &missing, &aliases).is_err());
+        let path = resolve_validated_context_path("tk").unwrap();
+        assert!(!store.context_file_exists(&path));
+
+        let clipboard = InMemoryClipboard::default();
+        clipboard.set_contents("keep me");
+        execute("tk", &TouchMode::Create, &store, &clipboard, &aliases).unwrap();
+        assert!(execute("tk", &TouchMode::Overwrite, &store, &missing, &aliases).is_err());
+        assert_eq!(store.read_context_contents(&path).unwrap(), "keep me");
+        assert!(store.list_trash().unwrap().is_empty());
+    }
+
+    #[test]
+    fn separators_render_between_entries() {
+        assert_eq!(AppendSeparator::None.join("a\n", "b", 0), "b");
+        assert_eq!(AppendSeparator::None.join("a", "b", 0), "\nb");
+        assert_eq!(AppendSeparator::None.join("", "b", 0), "b");
+        assert_eq!(
+            AppendSeparator::Timestamp.join("a\n", "b", 0),
+            "\n## 1970-01-01 00:00:00 UTC\n\nb"
+        );
+        assert_eq!(
+            AppendSeparator::parse("=== log ==="),
+            AppendSeparator::Custom("=== log ===".into())
+        );
+        assert_eq!(AppendSeparator::parse("rule"), AppendSeparator::Rule);
+    }
 }
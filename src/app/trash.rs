@@ -0,0 +1,109 @@
+use crate::context_files::{
+    context_key_for, resolve_validated_context_path_with, AliasRegistry, ContextFileStore,
+};
+use crate::error::AppError;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A trashed context file for `mx restore --list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrashEntry {
+    pub batch: String,
+    /// Path under `.mx/` the file is restored to, with `/` separators.
+    pub path: String,
+    /// The context key that restores it, if there is one.
+    pub key: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub trashed_at: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContextRestoreOutcome {
+    pub key: String,
+    /// Path under `.mx/`.
+    pub path: PathBuf,
+    pub trashed_at: u64,
+    /// The file existed and its contents were moved to the trash.
+    pub replaced: bool,
+}
+
+pub fn list(
+    store: &dyn ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<Vec<TrashEntry>, AppError> {
+    let entries = store
+        .list_trash()?
+        .into_iter()
+        .map(|file| TrashEntry {
+            batch: file.batch,
+            path: file
+                .relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            key: context_key_for(&file.relative_path, aliases),
+            trashed_at: file.trashed_at,
+            size: file.size,
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Brings back the newest trashed copy of the file `key` resolves to.
+pub fn restore(
+    key: &str,
+    store: &dyn ContextFileStore,
+    aliases: &AliasRegistry,
+) -> Result<ContextRestoreOutcome, AppError> {
+    let relative_path = resolve_validated_context_path_with(key, aliases)?;
+    let replaced = store.context_file_exists(&relative_path);
+    let file = store.restore_context_file(&relative_path)?;
+    Ok(ContextRestoreOutcome {
+        key: key.to_string(),
+        path: file.relative_path,
+        trashed_at: file.trashed_at,
+        replaced,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context_files::{resolve_validated_context_path, InMemoryContextStore};
+
+    fn write(store: &InMemoryContextStore, key: &str, contents: &str, force: bool) {
+        let path = resolve_validated_context_path(key).unwrap();
+        let status = store.prepare_context_file(&path, force).unwrap();
+        store.write_context_contents(&status.path, contents).unwrap();
+    }
+
+    #[test]
+    fn cleaned_and_overwritten_files_can_be_listed_and_restored() {
+        let store = InMemoryContextStore::default();
+        let aliases = AliasRegistry::builtin();
+        write(&store, "rq", "careful requirements", false);
+        write(&store, "rq", "accidental paste", true);
+        super::super::clean::execute(Some("rq".to_string()), &store, &aliases).unwrap();
+
+        let trashed = list(&store, &aliases).unwrap();
+        let summary: Vec<(&str, Option<&str>, u64)> = trashed
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.key.as_deref(), entry.size))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("requirements.md", Some("rq"), 16), ("requirements.md", Some("rq"), 20)]
+        );
+
+        let outcome = restore("rq", &store, &aliases).unwrap();
+        assert!(!outcome.replaced);
+        let outcome = restore("rq", &store, &aliases).unwrap();
+        assert!(outcome.replaced);
+
+        let path = resolve_validated_context_path_with("rq", &aliases).unwrap();
+        assert_eq!(store.read_context_contents(&path).unwrap(), "careful requirements");
+        assert!(restore("tk", &store, &aliases).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 use crate::error::AppError;
 use crate::snippets::SnippetCatalog;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct WhichOutcome {
     pub path: PathBuf,
+    /// The physical file or directory when a symlink under the commands root leads to `path`.
+    pub link_target: Option<PathBuf>,
 }
 
 pub fn execute(
@@ -16,15 +19,33 @@ pub fn execute(
         Some(snippet) => {
             let entry = catalog.resolve_snippet(snippet)?;
             let path = absolute_path(&entry.absolute_path)?;
-            Ok(WhichOutcome { path })
+            let link_target = link_target(&path, &absolute_path(commands_root)?);
+            Ok(WhichOutcome { path, link_target })
         }
         None => {
             let path = absolute_path(commands_root)?;
-            Ok(WhichOutcome { path })
+            let link_target = fs::read_link(&path).ok().and_then(|_| fs::canonicalize(&path).ok());
+            Ok(WhichOutcome { path, link_target })
         }
     }
 }
 
+/// Checks every component of `path` below `root` (or just the file, for snippets of synced
+/// sources) for a symlink, and returns where it physically lives if one is found.
+fn link_target(path: &Path, root: &Path) -> Option<PathBuf> {
+    let root = if path.starts_with(root) { root } else { path.parent()? };
+    let mut current = root.to_path_buf();
+    let linked = path.strip_prefix(root).ok()?.components().any(|component| {
+        current.push(component);
+        fs::symlink_metadata(&current).is_ok_and(|metadata| metadata.file_type().is_symlink())
+    });
+    if linked {
+        fs::canonicalize(path).ok()
+    } else {
+        None
+    }
+}
+
 fn absolute_path(path: &Path) -> Result<PathBuf, AppError> {
     if path.is_absolute() {
         return Ok(path.to_path_buf());
@@ -60,4 +81,27 @@ mod tests {
             execute(Some("wc"), &catalog, Path::new("/tmp/commands")).expect("should succeed");
         assert_eq!(outcome.path, snippet_path);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_reports_the_target_of_a_linked_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let team = dir.path().join("team-repo");
+        std::fs::create_dir_all(&team).unwrap();
+        std::fs::write(team.join("review.md"), "body").unwrap();
+        let commands_root = dir.path().join("commands");
+        std::fs::create_dir_all(&commands_root).unwrap();
+        std::os::unix::fs::symlink(&team, commands_root.join("team")).unwrap();
+        let catalog = InMemoryCatalog::new(vec![SnippetEntry {
+            key: "review".to_string(),
+            relative_path: "team/review".to_string(),
+            absolute_path: commands_root.join("team/review.md"),
+        }]);
+
+        let outcome = execute(Some("review"), &catalog, &commands_root).unwrap();
+
+        assert_eq!(outcome.path, commands_root.join("team/review.md"));
+        assert_eq!(outcome.link_target, Some(team.canonicalize().unwrap().join("review.md")));
+        assert!(execute(None, &catalog, &commands_root).unwrap().link_target.is_none());
+    }
 }
@@ -0,0 +1,85 @@
+use crate::app;
+use crate::context_files::{AliasRegistry, AliasSource, LocalContextFileStore, ResolutionStep};
+use crate::error::{AppError, ConfigError};
+use crate::project_fs::{CurrentDirectoryLocator, WorkspaceLocator};
+use serde::Serialize;
+
+pub(crate) fn run(json: bool) -> Result<(), AppError> {
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = LocalContextFileStore::new(workspace_root);
+    let entries = app::context_aliases(&aliases, &store);
+
+    if json {
+        return print_json(&entries);
+    }
+
+    if entries.is_empty() {
+        println!("(no context aliases)");
+        return Ok(());
+    }
+
+    let width = entries.iter().map(|entry| entry.key.len()).max().unwrap_or_default();
+    println!("🔑 Context keys:\n");
+    for entry in entries {
+        let marker = if entry.exists { "✅" } else { "  " };
+        println!(
+            "{marker} {:<width$}  .mx/{}  ({})",
+            entry.key,
+            entry.path,
+            source_label(entry.source)
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn resolve(key: &str, json: bool) -> Result<(), AppError> {
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = LocalContextFileStore::new(workspace_root);
+    let outcome = app::resolve_context_key(key, &aliases, &store);
+
+    if json {
+        return print_json(&outcome);
+    }
+
+    println!("🔎 {key}");
+    for step in &outcome.resolution.steps {
+        let description = match step {
+            ResolutionStep::PendingPrefix { remainder } => {
+                format!("'pd-' prefix: under pending/, continue with '{remainder}'")
+            }
+            ResolutionStep::Alias { key, path, source } => {
+                format!("{} alias '{key}' -> {path}", source_label(*source))
+            }
+            ResolutionStep::NumberedTasks { number } => {
+                format!("numbered tasks file 'tk{number}' -> tasks/tasks{number}.md")
+            }
+            ResolutionStep::Literal => "no alias: used as a path".to_string(),
+            ResolutionStep::ExtensionAdded => "no extension: added .md".to_string(),
+        };
+        println!("   {description}");
+    }
+    println!("-> .mx/{}", outcome.resolution.path.display());
+    match &outcome.error {
+        Some(error) => println!("⚠️ Not usable: {error}"),
+        None if outcome.exists => println!("✅ File exists"),
+        None => println!("(file does not exist yet)"),
+    }
+    Ok(())
+}
+
+fn source_label(source: AliasSource) -> &'static str {
+    match source {
+        AliasSource::Builtin => "built-in",
+        AliasSource::User => "user",
+        AliasSource::Project => "project",
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), AppError> {
+    let rendered = serde_json::to_string_pretty(value)
+        .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+    println!("{rendered}");
+    Ok(())
+}
@@ -1,19 +1,65 @@
 use crate::app;
+use crate::context_files::{AliasRegistry, LocalContextFileStore};
 use crate::error::AppError;
 use crate::project_fs::{CurrentDirectoryLocator, LocalWorkspaceFileReader, WorkspaceLocator};
-use crate::snippets::FilesystemSnippetCatalog;
+use crate::snippets::{parse_pin_slot, preferred_languages, FilesystemSnippetCatalog};
 
-pub(crate) fn run(snippet: &str) -> Result<(), AppError> {
-    let storage = FilesystemSnippetCatalog::from_env()?;
-    let workspace_store =
-        CurrentDirectoryLocator.find_workspace_root().ok().map(LocalWorkspaceFileReader::new);
-    let outcome = app::copy_snippet(snippet, &storage, workspace_store.as_ref())?;
+pub(crate) fn run(snippet: &str, strict: bool, lang: Option<&str>) -> Result<(), AppError> {
+    let mut storage = FilesystemSnippetCatalog::from_env()?;
+    if lang.is_some() {
+        storage = storage.with_languages(preferred_languages(lang));
+    }
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root().ok();
+    let workspace = workspace_root.as_ref().map(|root| root.display().to_string());
+    let workspace_store = workspace_root.clone().map(LocalWorkspaceFileReader::new);
+    let aliases = AliasRegistry::for_workspace(workspace_root.as_deref())?;
+    let context_store = workspace_root.map(LocalContextFileStore::new);
+    // `mx c 1` and `mx c :1` copy whatever is pinned to slot 1. Without a config dir there are
+    // no pins, and other queries never need the pin store.
+    let pinned = match parse_pin_slot(snippet).and_then(|_| super::pin_store().ok()) {
+        Some(pins) => app::resolve_pinned(snippet, &storage, &pins)?,
+        None => None,
+    };
+    let query = pinned.as_ref().map_or(snippet, |entry| entry.relative_path.as_str());
+    let outcome = app::copy_snippet(
+        query,
+        &storage,
+        workspace_store.as_ref(),
+        context_store.as_ref(),
+        &aliases,
+        strict,
+    )?;
 
+    // Copying is a read, so a broken history store must not fail it; recording here only
+    // catches edits made outside mx since the snippet was last seen. History tracks the base
+    // file, so copies of a language variant are not observed.
+    if let (Ok(history), None) = (super::snippet_history(&storage), &outcome.language) {
+        let _ = history.observe(&outcome.relative_path, &outcome.absolute_path, "observed");
+    }
+    // Same for usage tracking: the snippet is already on the clipboard.
+    if let Ok(Some(usage)) = super::usage_log(&storage) {
+        let _ = usage.record(&outcome.relative_path, &outcome.snippet, workspace);
+    }
+
+    let language = outcome.language.as_ref().map(|lang| format!(" [{lang}]")).unwrap_or_default();
     println!(
-        "✅ Copied '{}' from {} -> {}",
+        "✅ Copied '{}'{language} from {} -> {}",
         outcome.snippet,
         outcome.relative_path,
         outcome.absolute_path.display()
     );
+    if let Some(deprecation) = &outcome.deprecation {
+        let reason = deprecation.reason().map(|reason| format!(": {reason}")).unwrap_or_default();
+        eprintln!("⚠️ '{}' is deprecated{reason}", outcome.snippet);
+        if let Some(replacement) = deprecation.replacement() {
+            eprintln!("   Use '{replacement}' instead.");
+        }
+    }
+    if !outcome.missing_requirements.is_empty() {
+        eprintln!("⚠️ '{}' requires inputs that are not ready:", outcome.snippet);
+        for missing in &outcome.missing_requirements {
+            eprintln!("   {missing}");
+        }
+    }
     Ok(())
 }
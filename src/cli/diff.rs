@@ -0,0 +1,25 @@
+use crate::app::{self, DiffLine};
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(snippet: &str, revision: Option<u64>) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::diff_snippet(snippet, revision, &catalog, &history)?;
+
+    println!("--- {} (revision {})", outcome.relative_path, outcome.revision);
+    println!("+++ {} (current)", outcome.relative_path);
+    if !outcome.has_changes() {
+        println!("No changes.");
+        return Ok(());
+    }
+
+    for line in &outcome.lines {
+        match line {
+            DiffLine::Same(text) => println!(" {text}"),
+            DiffLine::Removed(text) => println!("-{text}"),
+            DiffLine::Added(text) => println!("+{text}"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::app;
+use crate::error::{AppError, ConfigError};
+use crate::snippets::FilesystemSnippetCatalog;
+use std::env;
+use std::process::Command;
+
+pub(crate) fn run(snippet: &str) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::edit_snippet(snippet, &catalog, &history, catalog.commands_root())?;
+
+    let editor =
+        env::var("EDITOR").or_else(|_| env::var("VISUAL")).unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| {
+        AppError::ConfigError(ConfigError::Other("EDITOR is set but empty".to_string()))
+    })?;
+
+    let status =
+        Command::new(program).args(parts).arg(&outcome.absolute_path).status().map_err(|err| {
+            AppError::ConfigError(ConfigError::Other(format!(
+                "Failed to run editor '{editor}': {err}"
+            )))
+        })?;
+    if !status.success() {
+        return Err(AppError::ConfigError(ConfigError::Other(format!(
+            "Editor '{editor}' exited with {status}"
+        ))));
+    }
+
+    history.observe(&outcome.relative_path, &outcome.absolute_path, "edit")?;
+
+    Ok(())
+}
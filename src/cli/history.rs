@@ -0,0 +1,23 @@
+use crate::app;
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+use crate::timestamp::format_utc;
+
+pub(crate) fn run(snippet: &str) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::snippet_history(snippet, &catalog, &history)?;
+
+    println!("History of {}:", outcome.relative_path);
+    let current = outcome.revisions.last().map(|revision| revision.id);
+    for revision in outcome.revisions.iter().rev() {
+        let marker = if Some(revision.id) == current { " (current)" } else { "" };
+        println!(
+            "  {:>4}  {}  {}{marker}",
+            revision.id,
+            format_utc(revision.recorded_at),
+            revision.reason
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::app::{self, Severity};
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(deny_warnings: bool) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let outcome = app::lint_snippets(&catalog)?;
+
+    for diagnostic in &outcome.diagnostics {
+        let location = match diagnostic.line {
+            Some(line) => format!("{}:{line}", diagnostic.relative_path),
+            None => diagnostic.relative_path.clone(),
+        };
+        let severity = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        println!("{location}: {severity}: {}", diagnostic.message);
+    }
+
+    let errors = outcome.error_count();
+    let warnings = outcome.warning_count();
+    if !outcome.diagnostics.is_empty() {
+        println!();
+    }
+    println!("Checked {} snippet(s): {errors} error(s), {warnings} warning(s)", outcome.checked);
+
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        return Err(AppError::LintFailed { errors, warnings });
+    }
+    Ok(())
+}
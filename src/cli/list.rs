@@ -1,23 +1,55 @@
-use crate::app;
-use crate::error::AppError;
+use super::ListOrder;
+use crate::app::{self, ListSort};
+use crate::error::{AppError, ConfigError};
 use crate::snippets::FilesystemSnippetCatalog;
 
-pub(crate) fn run() -> Result<(), AppError> {
+pub(crate) fn run(strict: bool, json: bool, order: ListOrder) -> Result<(), AppError> {
     let storage = FilesystemSnippetCatalog::from_env()?;
-    let entries = app::list_snippets(&storage)?;
-    if entries.is_empty() {
-        println!("(no snippets found)");
+    let sort = match order {
+        ListOrder::Name => ListSort::Name,
+        ListOrder::Recent => ListSort::Recent,
+        ListOrder::Frequent => ListSort::Frequent,
+    };
+    // Usage is only shown in JSON and only needed for the usage sorts.
+    let usage = if json || sort != ListSort::Name {
+        super::usage_log(&storage)?.map(|log| log.stats()).transpose()?
+    } else {
+        None
+    };
+    let outcome = app::list_snippets(&storage, strict, sort, usage.as_ref())?;
+
+    if json {
+        let rendered = serde_json::to_string_pretty(&outcome)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        println!("{rendered}");
         return Ok(());
     }
 
-    println!("📚 Available snippets:\n");
-    for app::ListEntry { snippet, relative_path, title, description } in entries {
-        println!("- {snippet} ({relative_path})");
-        if let Some(title) = title {
-            println!("  • {title}");
+    if outcome.entries.is_empty() {
+        println!("(no snippets found)");
+    } else {
+        println!("📚 Available snippets:\n");
+        for app::ListEntry { snippet, relative_path, title, description, variants, .. } in
+            outcome.entries
+        {
+            if variants.is_empty() {
+                println!("- {snippet} ({relative_path})");
+            } else {
+                println!("- {snippet} ({relative_path}) [{}]", variants.join(", "));
+            }
+            if let Some(title) = title {
+                println!("  • {title}");
+            }
+            if let Some(description) = description {
+                println!("    {description}");
+            }
         }
-        if let Some(description) = description {
-            println!("    {description}");
+    }
+
+    if !outcome.diagnostics.is_empty() {
+        println!("\n⚠️ Skipped {} snippet(s) with problems:\n", outcome.diagnostics.len());
+        for diagnostic in outcome.diagnostics {
+            println!("- {}: {}", diagnostic.relative_path, diagnostic.message);
         }
     }
 
@@ -0,0 +1,75 @@
+use crate::app::{self, ContextFileEntry};
+use crate::context_files::{AliasRegistry, LocalContextFileStore};
+use crate::error::{AppError, ConfigError};
+use crate::project_fs::{CurrentDirectoryLocator, WorkspaceLocator};
+use crate::timestamp::format_utc;
+use std::collections::BTreeMap;
+
+enum Node<'a> {
+    Dir(BTreeMap<&'a str, Node<'a>>),
+    File(&'a ContextFileEntry),
+}
+
+pub(crate) fn run(json: bool) -> Result<(), AppError> {
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = LocalContextFileStore::new(workspace_root);
+    let entries = app::list_context_files(&store, &aliases)?;
+
+    if json {
+        let rendered = serde_json::to_string_pretty(&entries)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        println!("{rendered}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("(no context files)");
+        return Ok(());
+    }
+
+    let mut root = BTreeMap::new();
+    for entry in &entries {
+        insert(&mut root, &entry.path, entry);
+    }
+    println!("📂 .mx");
+    print_tree(&root, "");
+    Ok(())
+}
+
+fn insert<'a>(dir: &mut BTreeMap<&'a str, Node<'a>>, path: &'a str, entry: &'a ContextFileEntry) {
+    match path.split_once('/') {
+        Some((name, rest)) => {
+            if let Node::Dir(children) =
+                dir.entry(name).or_insert_with(|| Node::Dir(BTreeMap::new()))
+            {
+                insert(children, rest, entry);
+            }
+        }
+        None => {
+            dir.insert(path, Node::File(entry));
+        }
+    }
+}
+
+fn print_tree(dir: &BTreeMap<&str, Node<'_>>, indent: &str) {
+    for (index, (name, node)) in dir.iter().enumerate() {
+        let last = index + 1 == dir.len();
+        let (branch, child_indent) =
+            if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        match node {
+            Node::Dir(children) => {
+                println!("{indent}{branch}{name}/");
+                print_tree(children, &format!("{indent}{child_indent}"));
+            }
+            Node::File(entry) => println!("{indent}{branch}{name}{}", describe(entry)),
+        }
+    }
+}
+
+fn describe(entry: &ContextFileEntry) -> String {
+    let key = entry.key.as_deref().map(|key| format!("  [{key}]")).unwrap_or_default();
+    let lines = if entry.lines == 1 { "line" } else { "lines" };
+    let modified = entry.modified.map(|time| format!(", {}", format_utc(time))).unwrap_or_default();
+    format!("{key}  {} {lines}, {} B{modified}", entry.lines, entry.size)
+}
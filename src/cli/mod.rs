@@ -1,12 +1,31 @@
+mod aliases;
 mod cat;
 mod clean;
 mod copy;
+mod diff;
+mod edit;
+mod history;
+mod lint;
 mod list;
+mod ls_context;
+mod mv;
+mod new;
+mod pack;
+mod pin;
+mod restore;
+mod rm;
+mod search;
+mod stats;
+mod sync;
 mod touch;
 mod which;
 
+use crate::config::{config_dir, UserConfig};
+use crate::context_files::LocalContextFileStore;
 use crate::error::AppError;
-use clap::{CommandFactory, Parser, Subcommand};
+use crate::snippets::{FilesystemSnippetCatalog, PinStore, SnippetHistory, UsageLog, UsageStats};
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "mx")]
@@ -20,33 +39,240 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     #[command(about = "List available snippets", visible_alias = "ls")]
-    List,
+    List {
+        #[arg(long = "strict", help = "Fail on the first snippet that cannot be read or parsed")]
+        strict: bool,
+        #[arg(long = "json")]
+        json: bool,
+        #[arg(long = "sort", value_enum, default_value = "name")]
+        sort: ListOrder,
+        #[arg(long = "pinned", help = "Show the pinned slots instead")]
+        pinned: bool,
+    },
     #[command(about = "Create context files", visible_alias = "t")]
     Touch {
         key: String,
         #[arg(short = 'f', long = "force")]
         force: bool,
+        #[arg(
+            short = 'a',
+            long = "append",
+            conflicts_with = "force",
+            help = "Add the content to the end of an existing file"
+        )]
+        append: bool,
+        #[arg(
+            long = "separator",
+            requires = "append",
+            help = "Line between appended entries: none, rule, timestamp or any text"
+        )]
+        separator: Option<String>,
+        #[arg(
+            long = "stdin",
+            conflicts_with_all = ["from", "cmd"],
+            help = "Read the content from standard input"
+        )]
+        stdin: bool,
+        #[arg(
+            long = "from",
+            value_name = "PATH",
+            conflicts_with = "cmd",
+            help = "Read the content from a file"
+        )]
+        from: Option<PathBuf>,
+        #[arg(long = "cmd", value_name = "COMMAND", help = "Store the output of a shell command")]
+        cmd: Option<String>,
     },
     #[command(about = "Display context file contents", visible_alias = "ct")]
     Cat { key: String },
     #[command(about = "Clean context files or directory", visible_alias = "cl")]
     Clean { key: Option<String> },
+    #[command(about = "List context keys, their paths and whether the files exist")]
+    Aliases {
+        #[arg(long = "json")]
+        json: bool,
+    },
+    #[command(about = "List files under .mx with their keys, sizes and modification times")]
+    LsContext {
+        #[arg(long = "json")]
+        json: bool,
+    },
+    #[command(about = "Explain how a context key resolves to a path")]
+    Resolve {
+        key: String,
+        #[arg(long = "json")]
+        json: bool,
+    },
     #[command(about = "Copy a snippet to the clipboard", visible_alias = "c")]
-    Copy { snippet: String },
+    Copy {
+        snippet: String,
+        #[arg(long = "strict", help = "Fail instead of copying when required inputs are missing")]
+        strict: bool,
+        #[arg(
+            long = "lang",
+            help = "Copy this language variant (overrides MX_LANG and the locale)"
+        )]
+        lang: Option<String>,
+    },
     #[command(about = "Print absolute path for commands root or snippet", visible_alias = "wh")]
-    Which { snippet: Option<String> },
+    Which {
+        snippet: Option<String>,
+        #[arg(short = 'v', long = "verbose", help = "Also show where a symlinked path leads")]
+        verbose: bool,
+    },
+    #[command(about = "Search snippet bodies and metadata", visible_alias = "s")]
+    Search {
+        query: String,
+        #[arg(short = 'e', long = "regex")]
+        regex: bool,
+        #[arg(short = 'i', long = "ignore-case")]
+        ignore_case: bool,
+        #[arg(short = 'C', long = "context", default_value_t = 1)]
+        context: usize,
+    },
+    #[command(about = "Create a snippet under the commands root", visible_alias = "n")]
+    New {
+        name: String,
+        #[arg(long = "from-clipboard", conflicts_with = "stdin")]
+        from_clipboard: bool,
+        #[arg(long = "stdin")]
+        stdin: bool,
+        #[arg(long = "title")]
+        title: Option<String>,
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    #[command(about = "Open a snippet in $EDITOR", visible_alias = "e")]
+    Edit { snippet: String },
+    #[command(about = "Move or rename a snippet and update includes that point at it")]
+    Mv { from: String, to: String },
+    #[command(about = "Move a snippet to the trash")]
+    Rm { snippet: String },
+    #[command(about = "Pin a snippet to a numbered slot, copied with `mx c <slot>`")]
+    Pin { slot: u8, snippet: String },
+    #[command(about = "Clear a pinned slot")]
+    Unpin { slot: u8 },
+    #[command(about = "List recorded revisions of a snippet")]
+    History { snippet: String },
+    #[command(about = "Show changes between a recorded revision and the current snippet")]
+    Diff {
+        snippet: String,
+        #[arg(help = "Revision to compare against (defaults to the previous one)")]
+        revision: Option<u64>,
+    },
+    #[command(
+        about = "Restore a snippet to a recorded revision, or a context file from the .mx trash",
+        group(ArgGroup::new("restore_target").required(true).args(["snippet", "context", "list"]))
+    )]
+    Restore {
+        #[arg(requires = "revision")]
+        snippet: Option<String>,
+        #[arg(help = "Revision to write back to the snippet")]
+        revision: Option<u64>,
+        /// Restore the newest trashed context file with this key.
+        #[arg(long, value_name = "KEY")]
+        context: Option<String>,
+        /// List the trashed context files.
+        #[arg(long)]
+        list: bool,
+    },
+    #[command(about = "Clone or fast-forward the git snippet sources from config.yaml")]
+    Sync { source: Option<String> },
+    #[command(about = "Share snippets as versioned pack archives")]
+    Pack {
+        #[command(subcommand)]
+        command: PackCommand,
+    },
+    #[command(about = "Show how often each snippet is copied")]
+    Stats,
+    #[command(about = "Validate every snippet in the commands root")]
+    Lint {
+        #[arg(long = "deny-warnings")]
+        deny_warnings: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackCommand {
+    #[command(about = "Bundle the snippets under a directory, or with a tag, into a pack")]
+    Export {
+        selector: String,
+        #[arg(short = 'o', long = "output")]
+        output: PathBuf,
+        #[arg(long = "version", help = "Pack version (defaults to a digest of the contents)")]
+        pack_version: Option<String>,
+        #[arg(long = "author", help = "Pack author (defaults to $USER)")]
+        author: Option<String>,
+    },
+    #[command(about = "Add the snippets of a pack to the commands root")]
+    Import {
+        pack: PathBuf,
+        #[arg(long = "prefix", help = "Directory to import the pack's snippets under")]
+        prefix: Option<String>,
+        #[arg(long = "on-conflict", value_enum, help = "Resolve collisions instead of refusing")]
+        on_conflict: Option<OnConflict>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ListOrder {
+    Name,
+    Recent,
+    Frequent,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OnConflict {
+    Skip,
+    Rename,
+    Overwrite,
 }
 
 pub fn run() {
     let cli = Cli::parse();
 
     let result = match cli.command {
-        Some(Commands::List) => list::run(),
-        Some(Commands::Touch { key, force }) => touch::run(&key, force),
+        Some(Commands::List { strict, json, sort, pinned }) => {
+            if pinned {
+                pin::list(json)
+            } else {
+                list::run(strict, json, sort)
+            }
+        }
+        Some(Commands::Touch { key, force, append, separator, stdin, from, cmd }) => {
+            touch::run(&key, force, append, separator.as_deref(), stdin, from, cmd)
+        }
         Some(Commands::Cat { key }) => cat::run(&key),
         Some(Commands::Clean { key }) => clean::run(key),
-        Some(Commands::Copy { snippet }) => copy::run(&snippet),
-        Some(Commands::Which { snippet }) => which::run(snippet.as_deref()),
+        Some(Commands::Aliases { json }) => aliases::run(json),
+        Some(Commands::Resolve { key, json }) => aliases::resolve(&key, json),
+        Some(Commands::LsContext { json }) => ls_context::run(json),
+        Some(Commands::Copy { snippet, strict, lang }) => {
+            copy::run(&snippet, strict, lang.as_deref())
+        }
+        Some(Commands::Which { snippet, verbose }) => which::run(snippet.as_deref(), verbose),
+        Some(Commands::Search { query, regex, ignore_case, context }) => {
+            search::run(&query, regex, ignore_case, context)
+        }
+        Some(Commands::New { name, from_clipboard, stdin, title, tags }) => {
+            new::run(&name, from_clipboard, stdin, title, tags)
+        }
+        Some(Commands::Edit { snippet }) => edit::run(&snippet),
+        Some(Commands::Mv { from, to }) => mv::run(&from, &to),
+        Some(Commands::Rm { snippet }) => rm::run(&snippet),
+        Some(Commands::Pin { slot, snippet }) => pin::run(slot, &snippet),
+        Some(Commands::Unpin { slot }) => pin::unpin(slot),
+        Some(Commands::History { snippet }) => history::run(&snippet),
+        Some(Commands::Diff { snippet, revision }) => diff::run(&snippet, revision),
+        Some(Commands::Restore { snippet: Some(snippet), revision: Some(revision), .. }) => {
+            restore::run(&snippet, revision)
+        }
+        Some(Commands::Restore { context: Some(key), .. }) => restore::context(&key),
+        Some(Commands::Restore { .. }) => restore::list_trash(),
+        Some(Commands::Sync { source }) => sync::run(source.as_deref()),
+        Some(Commands::Pack { command }) => pack::run(command),
+        Some(Commands::Stats) => stats::run(),
+        Some(Commands::Lint { deny_warnings }) => lint::run(deny_warnings),
         None => {
             Cli::command().print_help().ok();
             println!();
@@ -59,6 +285,37 @@ pub fn run() {
     }
 }
 
+/// History store in the catalog's state root, using the retention policy from the user config.
+fn snippet_history(catalog: &FilesystemSnippetCatalog) -> Result<SnippetHistory, AppError> {
+    let config = UserConfig::from_env_or_default()?;
+    Ok(SnippetHistory::new(catalog.history_root()?, config.history))
+}
+
+/// Context files of the workspace, trashed according to the user config.
+fn context_store(workspace_root: PathBuf) -> Result<LocalContextFileStore, AppError> {
+    let config = UserConfig::from_env_or_default()?;
+    Ok(LocalContextFileStore::new(workspace_root).with_trash(config.trash))
+}
+
+/// Pinned slots, kept next to the user config in `~/.config/mx/pins.yaml`.
+fn pin_store() -> Result<PinStore, AppError> {
+    Ok(PinStore::new(config_dir()?.join("pins.yaml")))
+}
+
+/// Usage log in the catalog's state root, or `None` when tracking is disabled.
+fn usage_log(catalog: &FilesystemSnippetCatalog) -> Result<Option<UsageLog>, AppError> {
+    let config = UserConfig::from_env_or_default()?;
+    if !config.usage.enabled {
+        return Ok(None);
+    }
+    Ok(Some(UsageLog::new(catalog.usage_path()?)))
+}
+
+/// Usage for ranking results. Ranking is a nicety, so an unreadable log ranks nothing.
+fn usage_stats(catalog: &FilesystemSnippetCatalog) -> UsageStats {
+    usage_log(catalog).ok().flatten().and_then(|log| log.stats().ok()).unwrap_or_default()
+}
+
 fn report_error_and_exit(err: AppError) {
     eprintln!("Error: {err}");
     std::process::exit(1);
@@ -0,0 +1,26 @@
+use crate::app;
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(from: &str, to: &str) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::move_snippet(from, to, &catalog, &history, catalog.commands_root())?;
+
+    if let Ok(Some(usage)) = super::usage_log(&catalog) {
+        let _ = usage.rename(&outcome.from, &outcome.to, &outcome.key);
+    }
+    // Pins only find a moved snippet again by a unique alias, so point them at it now.
+    if let Ok(pins) = super::pin_store() {
+        let _ = pins.rename(&outcome.from, &outcome.to, &outcome.key);
+    }
+
+    println!("✅ Moved {} -> {}", outcome.from, outcome.to);
+    for snippet in outcome.updated_snippets {
+        println!("   Updated references in {snippet}");
+    }
+    for (snippet, reason) in &outcome.failed_updates {
+        println!("⚠️ Could not update references in {snippet}: {reason}");
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::app::{self, NewSnippetOptions, SnippetSource};
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(
+    name: &str,
+    from_clipboard: bool,
+    stdin: bool,
+    title: Option<String>,
+    tags: Vec<String>,
+) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let source = if stdin {
+        SnippetSource::Text(std::io::read_to_string(std::io::stdin())?)
+    } else if from_clipboard {
+        SnippetSource::Clipboard
+    } else {
+        SnippetSource::Scaffold
+    };
+    let history = super::snippet_history(&catalog)?;
+    let options = NewSnippetOptions { title, tags };
+    let outcome =
+        app::new_snippet(name, source, &options, &catalog, &history, catalog.commands_root())?;
+
+    println!(
+        "✅ Created '{}' at {} -> {}",
+        outcome.snippet,
+        outcome.relative_path,
+        outcome.absolute_path.display()
+    );
+    Ok(())
+}
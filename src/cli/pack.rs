@@ -0,0 +1,62 @@
+use super::{OnConflict, PackCommand};
+use crate::app::{self, ConflictStrategy, ImportAction, PackExportOptions, PackImportOptions};
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+use std::env;
+
+pub(crate) fn run(command: PackCommand) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+
+    match command {
+        PackCommand::Export { selector, output, pack_version, author } => {
+            let options = PackExportOptions {
+                version: pack_version,
+                author: author.or_else(|| env::var("USER").ok()),
+            };
+            let manifest =
+                app::export_pack(&selector, &output, &options, &catalog, catalog.commands_root())?;
+
+            println!(
+                "📦 Exported {} snippet(s) as version {} -> {}",
+                manifest.entries.len(),
+                manifest.version,
+                output.display()
+            );
+        }
+        PackCommand::Import { pack, prefix, on_conflict } => {
+            let history = super::snippet_history(&catalog)?;
+            let options = PackImportOptions {
+                prefix,
+                on_conflict: on_conflict.map(|strategy| match strategy {
+                    OnConflict::Skip => ConflictStrategy::Skip,
+                    OnConflict::Rename => ConflictStrategy::Rename,
+                    OnConflict::Overwrite => ConflictStrategy::Overwrite,
+                }),
+            };
+            let outcome =
+                app::import_pack(&pack, &options, &catalog, &history, catalog.commands_root())?;
+
+            let author = outcome.manifest.author.as_deref().unwrap_or("unknown author");
+            println!("📦 Pack version {} by {author}", outcome.manifest.version);
+            for entry in &outcome.entries {
+                match entry.action {
+                    ImportAction::Added => println!("   Added {}", entry.relative_path),
+                    ImportAction::Skipped => println!("   Skipped {}", entry.relative_path),
+                    ImportAction::Renamed => {
+                        println!("   Renamed {} -> {}", entry.pack_path, entry.relative_path)
+                    }
+                    ImportAction::Overwritten => {
+                        println!("   Overwrote {}", entry.relative_path)
+                    }
+                }
+            }
+            let written = outcome
+                .entries
+                .iter()
+                .filter(|entry| entry.action != ImportAction::Skipped)
+                .count();
+            println!("✅ Imported {written} of {} snippet(s)", outcome.entries.len());
+        }
+    }
+    Ok(())
+}
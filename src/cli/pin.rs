@@ -0,0 +1,47 @@
+use crate::app;
+use crate::error::{AppError, ConfigError};
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(slot: u8, snippet: &str) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let outcome = app::pin_snippet(slot, snippet, &catalog, &super::pin_store()?)?;
+
+    println!("📌 Pinned {} to slot {}", outcome.relative_path, outcome.slot);
+    if let Some(replaced) = outcome.replaced.filter(|pin| pin.path != outcome.relative_path) {
+        println!("   Replaced {}", replaced.path);
+    }
+    Ok(())
+}
+
+pub(crate) fn unpin(slot: u8) -> Result<(), AppError> {
+    let removed = app::unpin_snippet(slot, &super::pin_store()?)?;
+    println!("✅ Cleared slot {slot} ({})", removed.path);
+    Ok(())
+}
+
+pub(crate) fn list(json: bool) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let entries = app::pinned_snippets(&catalog, &super::pin_store()?)?;
+
+    if json {
+        let rendered = serde_json::to_string_pretty(&entries)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        println!("{rendered}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("(no pinned snippets; pin one with `mx pin 1 <snippet>`)");
+        return Ok(());
+    }
+
+    println!("📌 Pinned snippets:\n");
+    for entry in entries {
+        let missing = if entry.missing { " ⚠️ missing" } else { "" };
+        println!("{}: {} ({}){missing}", entry.slot, entry.snippet, entry.relative_path);
+        if let Some(title) = entry.title {
+            println!("   • {title}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::app;
+use crate::context_files::{AliasRegistry, TRASH_DIR};
+use crate::error::AppError;
+use crate::project_fs::{CurrentDirectoryLocator, WorkspaceLocator};
+use crate::snippets::FilesystemSnippetCatalog;
+use crate::timestamp::format_utc;
+
+pub(crate) fn run(snippet: &str, revision: u64) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::restore_snippet(snippet, revision, &catalog, &history)?;
+
+    println!(
+        "✅ Restored {} to revision {} -> {}",
+        outcome.relative_path,
+        outcome.revision,
+        outcome.absolute_path.display()
+    );
+    Ok(())
+}
+
+pub(crate) fn context(key: &str) -> Result<(), AppError> {
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = super::context_store(workspace_root)?;
+    let outcome = app::restore_context(key, &store, &aliases)?;
+
+    println!(
+        "✅ Restored .mx/{} from the trash ({})",
+        outcome.path.display(),
+        format_utc(outcome.trashed_at)
+    );
+    if outcome.replaced {
+        println!("🗑️ The previous contents were moved to .mx/{TRASH_DIR}");
+    }
+    Ok(())
+}
+
+pub(crate) fn list_trash() -> Result<(), AppError> {
+    let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = super::context_store(workspace_root)?;
+    let entries = app::list_trash(&store, &aliases)?;
+
+    if entries.is_empty() {
+        println!("(trash is empty)");
+        return Ok(());
+    }
+
+    println!("🗑️ .mx/{TRASH_DIR}, newest first:\n");
+    for entry in entries {
+        let key = entry.key.map(|key| format!("  [{key}]")).unwrap_or_default();
+        println!("  {}  {}{key}  {} B", format_utc(entry.trashed_at), entry.path, entry.size);
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::app;
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(snippet: &str) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let history = super::snippet_history(&catalog)?;
+    let outcome = app::remove_snippet(
+        snippet,
+        &catalog,
+        &history,
+        catalog.commands_root(),
+        &catalog.trash_root()?,
+    )?;
+
+    println!("🗑️ Moved {} to trash: {}", outcome.relative_path, outcome.trash_path.display());
+    // The snippet is gone, so a pin on it would only fail later or match something else.
+    if let Ok(slots) = super::pin_store().and_then(|pins| pins.remove_path(&outcome.relative_path))
+    {
+        for slot in slots {
+            println!("   Unpinned slot {slot}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::app::{self, SearchField, SearchMatch, SearchOptions};
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+
+pub(crate) fn run(
+    query: &str,
+    regex: bool,
+    ignore_case: bool,
+    context_lines: usize,
+) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let options = SearchOptions { regex, ignore_case, context_lines };
+    let usage = super::usage_stats(&catalog);
+    let hits = app::search_snippets(query, &options, &catalog, &catalog.roots(), &usage)?;
+    if hits.is_empty() {
+        println!("(no matches for '{query}')");
+        return Ok(());
+    }
+
+    println!("🔎 Matches for '{query}':\n");
+    for hit in hits {
+        println!("- {} ({})", hit.relative_path, hit.snippet);
+        for found in hit.matches {
+            match found.field {
+                SearchField::Title => println!("  title: {}", found.line),
+                SearchField::Description => println!("  description: {}", found.line),
+                SearchField::Body => print_body_match(&found),
+            }
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_body_match(found: &SearchMatch) {
+    let line_number = found.line_number.unwrap_or_default();
+    let first = line_number - found.before.len();
+    for (offset, line) in found.before.iter().enumerate() {
+        println!("  {}- {line}", first + offset);
+    }
+    println!("  {line_number}: {}", found.line);
+    for (offset, line) in found.after.iter().enumerate() {
+        println!("  {}- {line}", line_number + 1 + offset);
+    }
+}
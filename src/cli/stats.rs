@@ -0,0 +1,34 @@
+use crate::app;
+use crate::error::AppError;
+use crate::snippets::FilesystemSnippetCatalog;
+use crate::timestamp::format_utc;
+
+pub(crate) fn run() -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let Some(usage) = super::usage_log(&catalog)? else {
+        println!("Usage tracking is disabled (usage.enabled in config.yaml).");
+        return Ok(());
+    };
+    let outcome = app::snippet_stats(&catalog, &usage.stats()?)?;
+
+    if outcome.used.is_empty() {
+        println!("(no recorded copies yet)");
+    } else {
+        println!("📊 Snippet usage:\n");
+        for entry in &outcome.used {
+            let last_used = entry.last_used.map(format_utc).unwrap_or_default();
+            println!(
+                "- {} ({}): {} use(s), last {last_used}",
+                entry.relative_path, entry.snippet, entry.uses
+            );
+        }
+    }
+
+    if !outcome.unused.is_empty() {
+        println!("\n💤 Never copied ({}):\n", outcome.unused.len());
+        for relative_path in &outcome.unused {
+            println!("- {relative_path}");
+        }
+    }
+    Ok(())
+}
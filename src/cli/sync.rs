@@ -0,0 +1,41 @@
+use crate::app;
+use crate::config::UserConfig;
+use crate::error::{AppError, ConfigError};
+use crate::snippets::FilesystemSnippetCatalog;
+use crate::sources::SyncStatus;
+
+pub(crate) fn run(source: Option<&str>) -> Result<(), AppError> {
+    let catalog = FilesystemSnippetCatalog::from_env()?;
+    let config = UserConfig::from_env_or_default()?;
+    if config.sources.is_empty() {
+        println!("No sources configured. Add a `sources:` list to config.yaml.");
+        return Ok(());
+    }
+
+    let results = app::sync_sources(&config.sources, source, &catalog.sources_root()?)?;
+    let mut failed = 0;
+    for result in results {
+        match result.status {
+            Ok(SyncStatus::Cloned { commit }) => {
+                println!("✅ {}: cloned at {commit}", result.name)
+            }
+            Ok(SyncStatus::Updated { from, to }) => {
+                println!("✅ {}: updated {from} -> {to}", result.name)
+            }
+            Ok(SyncStatus::UpToDate { commit }) => {
+                println!("✅ {}: already up to date at {commit}", result.name)
+            }
+            Err(err) => {
+                failed += 1;
+                eprintln!("❌ {err}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::ConfigError(ConfigError::Other(format!(
+            "{failed} source(s) failed to sync"
+        ))));
+    }
+    Ok(())
+}
@@ -1,14 +1,42 @@
-use crate::app;
-use crate::context_files::LocalContextFileStore;
+use crate::app::{self, AppendSeparator, TouchMode, TouchSource};
+use crate::context_files::AliasRegistry;
 use crate::error::AppError;
 use crate::project_fs::{CurrentDirectoryLocator, WorkspaceLocator};
+use std::path::PathBuf;
 
-pub(crate) fn run(key: &str, force: bool) -> Result<(), AppError> {
+pub(crate) fn run(
+    key: &str,
+    force: bool,
+    append: bool,
+    separator: Option<&str>,
+    stdin: bool,
+    from: Option<PathBuf>,
+    cmd: Option<String>,
+) -> Result<(), AppError> {
+    let source = if stdin {
+        TouchSource::Stdin
+    } else if let Some(path) = from {
+        TouchSource::File(path)
+    } else if let Some(command) = cmd {
+        TouchSource::Command(command)
+    } else {
+        TouchSource::Clipboard
+    };
+    let mode = if append {
+        TouchMode::Append(separator.map(AppendSeparator::parse).unwrap_or_default())
+    } else if force {
+        TouchMode::Overwrite
+    } else {
+        TouchMode::Create
+    };
     let workspace_root = CurrentDirectoryLocator.find_workspace_root()?;
-    let store = LocalContextFileStore::new(workspace_root);
-    let outcome = app::touch_context(key, force, &store)?;
+    let aliases = AliasRegistry::for_workspace(Some(&workspace_root))?;
+    let store = super::context_store(workspace_root)?;
+    let outcome = app::touch_context(key, &mode, source, &store, &aliases)?;
 
-    if outcome.overwritten {
+    if outcome.appended {
+        println!("✅ Appended to context file: {}", outcome.path.display());
+    } else if outcome.overwritten {
         println!("✅ Context file overwritten: {}", outcome.path.display());
     } else if outcome.existed {
         println!("⚠️ Context file already exists: {}", outcome.path.display());
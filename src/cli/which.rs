@@ -2,9 +2,12 @@ use crate::app;
 use crate::error::AppError;
 use crate::snippets::FilesystemSnippetCatalog;
 
-pub(crate) fn run(snippet: Option<&str>) -> Result<(), AppError> {
+pub(crate) fn run(snippet: Option<&str>, verbose: bool) -> Result<(), AppError> {
     let catalog = FilesystemSnippetCatalog::from_env()?;
     let outcome = app::which_path(snippet, &catalog, catalog.commands_root())?;
-    println!("{}", outcome.path.display());
+    match outcome.link_target.filter(|_| verbose) {
+        Some(target) => println!("{} -> {}", outcome.path.display(), target.display()),
+        None => println!("{}", outcome.path.display()),
+    }
     Ok(())
 }
@@ -0,0 +1,113 @@
+use crate::clipboard::Clipboard;
+use crate::error::{AppError, NotFoundError};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Text to store in a context file. Any clipboard is a source; the others let `mx touch` run
+/// where no clipboard exists, such as over SSH or in CI.
+pub trait ContentSource {
+    fn read(&self) -> Result<String, AppError>;
+}
+
+impl<C: Clipboard + ?Sized> ContentSource for C {
+    fn read(&self) -> Result<String, AppError> {
+        self.paste()
+    }
+}
+
+/// Everything piped into mx: `cargo test 2>&1 | mx t er --stdin`.
+pub struct StdinSource;
+
+impl ContentSource for StdinSource {
+    fn read(&self) -> Result<String, AppError> {
+        Ok(io::read_to_string(io::stdin())?)
+    }
+}
+
+/// The contents of a file, relative to the current directory.
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl ContentSource for FileSource {
+    fn read(&self) -> Result<String, AppError> {
+        fs::read_to_string(&self.path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => {
+                AppError::NotFound(NotFoundError::File(self.path.display().to_string()))
+            }
+            _ => err.into(),
+        })
+    }
+}
+
+/// The output of a shell command. Stdout and stderr share one pipe, so the output keeps the
+/// order the command wrote it in. A failing command still yields its output, since failures
+/// are usually what gets saved.
+pub struct CommandSource {
+    command: String,
+}
+
+impl CommandSource {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self { command: command.into() }
+    }
+}
+
+impl ContentSource for CommandSource {
+    fn read(&self) -> Result<String, AppError> {
+        let failed =
+            |source: io::Error| AppError::CommandFailed { command: self.command.clone(), source };
+        let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+        let (mut reader, writer) = io::pipe().map_err(failed)?;
+        // The command must be dropped once spawned: it holds write ends of the pipe, and the
+        // read below only ends when every write end is closed.
+        let mut child = {
+            let mut command = Command::new(shell);
+            command
+                .args([flag, &self.command])
+                .stdin(Stdio::null())
+                .stdout(writer.try_clone().map_err(failed)?)
+                .stderr(writer);
+            command.spawn().map_err(failed)?
+        };
+
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output)?;
+        child.wait()?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn file_source_reads_the_file_and_reports_a_missing_one() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        fs::write(&path, "panic").unwrap();
+
+        assert_eq!(FileSource::new(path).read().unwrap(), "panic");
+        assert!(matches!(
+            FileSource::new(dir.path().join("none.txt")).read(),
+            Err(AppError::NotFound(NotFoundError::File(_)))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_source_keeps_the_output_of_a_failing_command_in_order() {
+        let source = CommandSource::new("echo err >&2; echo out; echo err2 >&2; exit 3");
+        assert_eq!(source.read().unwrap(), "err\nout\nerr2\n");
+    }
+}
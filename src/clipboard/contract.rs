@@ -4,3 +4,13 @@ pub trait Clipboard {
     fn copy(&self, text: &str) -> Result<(), AppError>;
     fn paste(&self) -> Result<String, AppError>;
 }
+
+impl<C: Clipboard + ?Sized> Clipboard for Box<C> {
+    fn copy(&self, text: &str) -> Result<(), AppError> {
+        (**self).copy(text)
+    }
+
+    fn paste(&self) -> Result<String, AppError> {
+        (**self).paste()
+    }
+}
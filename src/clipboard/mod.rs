@@ -1,3 +1,4 @@
+mod content_source;
 mod contract;
 mod file_clipboard;
 mod system_clipboard;
@@ -9,6 +10,7 @@ use crate::error::AppError;
 use std::env;
 use std::path::PathBuf;
 
+pub use content_source::{CommandSource, ContentSource, FileSource, StdinSource};
 pub use contract::Clipboard;
 pub use file_clipboard::FileClipboard;
 pub use system_clipboard::SystemClipboard;
@@ -0,0 +1,3 @@
+mod user_config;
+
+pub use user_config::{config_dir, UserConfig};
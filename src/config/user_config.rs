@@ -0,0 +1,183 @@
+use crate::context_files::TrashConfig;
+use crate::error::{AppError, ConfigError};
+use crate::snippets::{CatalogConfig, RetentionPolicy, UsageConfig};
+use crate::sources::GitSource;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = "config.yaml";
+
+/// Settings read from `~/.config/mx/config.yaml`. Every section is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserConfig {
+    pub catalog: CatalogConfig,
+    pub history: RetentionPolicy,
+    pub sources: Vec<GitSource>,
+    pub trash: TrashConfig,
+    pub usage: UsageConfig,
+}
+
+impl UserConfig {
+    pub fn from_env() -> Result<Self, AppError> {
+        Self::load(&config_dir()?.join(CONFIG_FILE))
+    }
+
+    /// Like [`UserConfig::from_env`], but falls back to defaults when `HOME` is unset. A config
+    /// file that exists but is invalid is still an error.
+    pub fn from_env_or_default() -> Result<Self, AppError> {
+        match config_dir() {
+            Ok(dir) => Self::load(&dir.join(CONFIG_FILE)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Reads the config at `path`, falling back to defaults when the file does not exist.
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let invalid = |message: String| {
+            AppError::ConfigError(ConfigError::InvalidConfigFile {
+                path: path.display().to_string(),
+                message,
+            })
+        };
+        let config: Self =
+            serde_yaml::from_str(&content).map_err(|err| invalid(err.to_string()))?;
+        config.validate_sources().map_err(invalid)?;
+        Ok(config)
+    }
+
+    /// Source names become the top-level directory their snippets are mounted under, so they
+    /// must be unique single path segments.
+    fn validate_sources(&self) -> Result<(), String> {
+        let mut seen = BTreeSet::new();
+        for source in &self.sources {
+            let name = source.name.as_str();
+            let valid = !name.is_empty()
+                && name != "."
+                && name != ".."
+                && !name.starts_with('.')
+                && !name.contains(['/', '\\']);
+            if !valid {
+                return Err(format!("source name '{name}' must be a single directory name"));
+            }
+            if !seen.insert(name) {
+                return Err(format!("source name '{name}' is declared more than once"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The mx configuration directory, `~/.config/mx`.
+pub fn config_dir() -> Result<PathBuf, AppError> {
+    let home = env::var("HOME")
+        .map_err(|_| AppError::ConfigError(ConfigError::MissingEnvVar("HOME".to_string())))?;
+    Ok(PathBuf::from(home).join(".config").join("mx"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn load_returns_defaults_when_file_is_missing() {
+        let dir = tempdir().unwrap();
+        let config = UserConfig::load(&dir.path().join(CONFIG_FILE)).unwrap();
+        assert_eq!(config.history, RetentionPolicy::default());
+    }
+
+    #[test]
+    fn load_reads_history_retention() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(&path, "history:\n  max_revisions: 3\n  max_age_days: 7\n").unwrap();
+
+        let config = UserConfig::load(&path).unwrap();
+        assert_eq!(config.history.max_revisions, 3);
+        assert_eq!(config.history.max_age_days, Some(7));
+    }
+
+    #[test]
+    fn load_reads_git_sources() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "sources:\n  - name: team\n    url: file:///srv/prompts.git\n    ref: main\n",
+        )
+        .unwrap();
+
+        let config = UserConfig::load(&path).unwrap();
+        assert_eq!(
+            config.sources,
+            vec![GitSource {
+                name: "team".to_string(),
+                url: "file:///srv/prompts.git".to_string(),
+                reference: Some("main".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn load_rejects_unusable_source_names() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        for name in ["../up", "a/b", "", ".git"] {
+            fs::write(&path, format!("sources:\n  - name: '{name}'\n    url: x\n")).unwrap();
+            assert!(UserConfig::load(&path).is_err(), "{name} should be rejected");
+        }
+
+        fs::write(&path, "sources:\n  - {name: a, url: x}\n  - {name: a, url: y}\n").unwrap();
+        let err = UserConfig::load(&path).unwrap_err();
+        assert!(err.to_string().contains("declared more than once"));
+    }
+
+    #[test]
+    fn load_reads_usage_settings() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        assert!(UserConfig::load(&path).unwrap().usage.enabled);
+
+        fs::write(&path, "usage:\n  enabled: false\n").unwrap();
+        let config = UserConfig::load(&path).unwrap();
+        assert!(!config.usage.enabled);
+        assert!(!config.usage.resolve_ambiguous);
+    }
+
+    #[test]
+    fn load_reads_context_trash_retention() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        assert_eq!(UserConfig::load(&path).unwrap().trash, TrashConfig::default());
+
+        fs::write(&path, "trash:\n  max_batches: 0\n  max_age_days: null\n").unwrap();
+        let config = UserConfig::load(&path).unwrap();
+        assert!(!config.trash.enabled());
+        assert_eq!(config.trash.max_age_days, None);
+    }
+
+    #[test]
+    fn load_rejects_unknown_sections() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(&path, "histroy:\n  max_revisions: 3\n").unwrap();
+
+        let err = UserConfig::load(&path).unwrap_err();
+        assert!(matches!(err, AppError::ConfigError(ConfigError::InvalidConfigFile { .. })));
+    }
+}
@@ -1,24 +1,281 @@
-pub fn resolve_alias(key: &str) -> Option<&'static str> {
-    match key {
-        "tk" => Some("tasks.md"),
-        "atk" => Some("additional_tasks.md"),
-        "tko" => Some("tasks_outline.md"),
-        "is" => Some("issue.md"),
-        "rq" => Some("requirements.md"),
-        "rv" => Some("review.md"),
-        "df" => Some("diff.md"),
-        "pdt" => Some("pending/tasks.md"),
-        "pdr" => Some("pending/requirements.md"),
-        "wn" => Some("warnings.md"),
-        "er" => Some("error.md"),
-        "if" => Some("info.md"),
-        "aif" => Some("additional_info.md"),
-        "rf" => Some("reference.md"),
-        "rp" => Some("report.md"),
-        "pl" => Some("plan.md"),
-        "sg" => Some("suggestion.md"),
-        "sm" => Some("summary.md"),
-        "cg" => Some("changes.md"),
-        _ => None,
+use crate::config::config_dir;
+use crate::error::{AppError, ConfigError};
+use crate::project_fs::SafePath;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const USER_ALIASES_FILE: &str = "aliases.toml";
+/// Project settings committed at the workspace root.
+pub const PROJECT_CONFIG_FILE: &str = ".mx.toml";
+
+const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("tk", "tasks.md"),
+    ("atk", "additional_tasks.md"),
+    ("tko", "tasks_outline.md"),
+    ("is", "issue.md"),
+    ("rq", "requirements.md"),
+    ("rv", "review.md"),
+    ("df", "diff.md"),
+    ("pdt", "pending/tasks.md"),
+    ("pdr", "pending/requirements.md"),
+    ("wn", "warnings.md"),
+    ("er", "error.md"),
+    ("if", "info.md"),
+    ("aif", "additional_info.md"),
+    ("rf", "reference.md"),
+    ("rp", "report.md"),
+    ("pl", "plan.md"),
+    ("sg", "suggestion.md"),
+    ("sm", "summary.md"),
+    ("cg", "changes.md"),
+];
+
+/// Where an alias was defined. Later layers override earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AliasSource {
+    Builtin,
+    /// `~/.config/mx/aliases.toml`.
+    User,
+    /// `.mx.toml` at the workspace root.
+    Project,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextAlias {
+    pub key: String,
+    /// Path under `.mx/`.
+    pub path: String,
+    pub source: AliasSource,
+}
+
+/// A value in an `[aliases]` table: the target path, or `false` to drop the alias.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AliasValue {
+    Path(String),
+    Enabled(bool),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AliasFile {
+    aliases: BTreeMap<String, AliasValue>,
+}
+
+/// Context keys such as `tk` and the `.mx/` paths they stand for: the built-in set, with the
+/// user and project files layered on top that add, override or disable entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasRegistry {
+    aliases: BTreeMap<String, ContextAlias>,
+}
+
+impl Default for AliasRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl AliasRegistry {
+    pub fn builtin() -> Self {
+        let aliases = BUILTIN_ALIASES
+            .iter()
+            .map(|(key, path)| {
+                let alias = ContextAlias {
+                    key: key.to_string(),
+                    path: path.to_string(),
+                    source: AliasSource::Builtin,
+                };
+                (key.to_string(), alias)
+            })
+            .collect();
+        Self { aliases }
+    }
+
+    /// The built-in aliases with `~/.config/mx/aliases.toml` applied. Without a config dir
+    /// (no `HOME`) there is no user file, so only the built-in aliases apply.
+    pub fn from_env() -> Result<Self, AppError> {
+        match config_dir() {
+            Ok(dir) => Self::builtin().layer(&dir.join(USER_ALIASES_FILE), AliasSource::User),
+            Err(_) => Ok(Self::builtin()),
+        }
+    }
+
+    /// The aliases in effect inside `workspace_root`: project over user over built-in.
+    pub fn for_workspace(workspace_root: Option<&Path>) -> Result<Self, AppError> {
+        let registry = Self::from_env()?;
+        match workspace_root {
+            Some(root) => registry.layer(&root.join(PROJECT_CONFIG_FILE), AliasSource::Project),
+            None => Ok(registry),
+        }
+    }
+
+    /// Applies the `[aliases]` table of the TOML file at `path`, if it exists. Every entry is
+    /// checked before any is applied, so a broken file changes nothing.
+    pub fn layer(mut self, path: &Path, source: AliasSource) -> Result<Self, AppError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(self),
+            Err(err) => return Err(err.into()),
+        };
+        let invalid = |message: String| {
+            AppError::ConfigError(ConfigError::InvalidConfigFile {
+                path: path.display().to_string(),
+                message,
+            })
+        };
+        let file: AliasFile = toml::from_str(&content).map_err(|err| invalid(err.to_string()))?;
+
+        let mut changes = Vec::new();
+        for (key, value) in file.aliases {
+            validate_key(&key).map_err(invalid)?;
+            let target = match value {
+                AliasValue::Path(target) => {
+                    validate_target(&key, &target).map_err(invalid)?;
+                    Some(target)
+                }
+                AliasValue::Enabled(false) => None,
+                AliasValue::Enabled(true) => {
+                    return Err(invalid(format!(
+                        "alias '{key}' must be a path under .mx, or false to disable it"
+                    )))
+                }
+            };
+            changes.push((key, target));
+        }
+
+        for (key, target) in changes {
+            match target {
+                Some(path) => {
+                    self.aliases.insert(key.clone(), ContextAlias { key, path, source });
+                }
+                None => {
+                    self.aliases.remove(&key);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ContextAlias> {
+        self.aliases.get(key)
+    }
+
+    pub fn resolve(&self, key: &str) -> Option<&str> {
+        self.get(key).map(|alias| alias.path.as_str())
+    }
+
+    /// Every active alias, sorted by key.
+    pub fn aliases(&self) -> impl Iterator<Item = &ContextAlias> {
+        self.aliases.values()
+    }
+}
+
+/// Keys are typed on the command line and cannot start with the `pd-` pending prefix, which is
+/// stripped before aliases are looked up.
+fn validate_key(key: &str) -> Result<(), String> {
+    let valid = !key.is_empty()
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !key.starts_with("pd-");
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "alias key '{key}' must be letters, digits, '-' or '_' and not start with 'pd-'"
+        ))
+    }
+}
+
+fn validate_target(key: &str, target: &str) -> Result<(), String> {
+    SafePath::try_from_path(Path::new(target))
+        .map(|_| ())
+        .map_err(|err| format!("alias '{key}' points outside .mx: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn builtin_aliases_resolve_without_config() {
+        let registry = AliasRegistry::builtin();
+        assert_eq!(registry.resolve("tk"), Some("tasks.md"));
+        assert_eq!(registry.resolve("pdr"), Some("pending/requirements.md"));
+        assert_eq!(registry.resolve("adr"), None);
+        assert_eq!(registry.aliases().count(), BUILTIN_ALIASES.len());
+    }
+
+    #[test]
+    fn layer_adds_overrides_and_disables_entries() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(USER_ALIASES_FILE);
+        fs::write(&path, "[aliases]\nadr = \"docs/adr.md\"\ntk = \"todo.md\"\ndf = false\n")
+            .unwrap();
+
+        let registry = AliasRegistry::builtin().layer(&path, AliasSource::User).unwrap();
+
+        assert_eq!(registry.resolve("adr"), Some("docs/adr.md"));
+        assert_eq!(registry.resolve("tk"), Some("todo.md"));
+        assert_eq!(registry.resolve("df"), None);
+        let tk = registry.aliases().find(|alias| alias.key == "tk").unwrap();
+        assert_eq!(tk.source, AliasSource::User);
+        assert!(AliasRegistry::builtin()
+            .layer(&dir.path().join("none.toml"), AliasSource::User)
+            .is_ok());
+    }
+
+    #[test]
+    fn project_layer_wins_over_user_layer() {
+        let dir = tempdir().unwrap();
+        let user = dir.path().join(USER_ALIASES_FILE);
+        let project = dir.path().join(PROJECT_CONFIG_FILE);
+        fs::write(&user, "[aliases]\napi = \"user-api.md\"\nadr = \"docs/adr.md\"\n").unwrap();
+        fs::write(&project, "[aliases]\napi = \"api-contract.md\"\n").unwrap();
+
+        let registry = AliasRegistry::builtin()
+            .layer(&user, AliasSource::User)
+            .and_then(|registry| registry.layer(&project, AliasSource::Project))
+            .unwrap();
+
+        assert_eq!(registry.resolve("api"), Some("api-contract.md"));
+        assert_eq!(registry.resolve("adr"), Some("docs/adr.md"));
+        let sources: Vec<(&str, AliasSource)> = registry
+            .aliases()
+            .filter(|alias| ["api", "adr", "tk"].contains(&alias.key.as_str()))
+            .map(|alias| (alias.key.as_str(), alias.source))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("adr", AliasSource::User),
+                ("api", AliasSource::Project),
+                ("tk", AliasSource::Builtin)
+            ]
+        );
+    }
+
+    #[test]
+    fn layer_rejects_traversal_duplicates_and_bad_keys() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(USER_ALIASES_FILE);
+        for content in [
+            "[aliases]\nup = \"../secrets.md\"\n",
+            "[aliases]\nabs = \"/etc/passwd\"\n",
+            "[aliases]\nadr = \"a.md\"\nadr = \"b.md\"\n",
+            "[aliases]\n\"a/b\" = \"x.md\"\n",
+            "[aliases]\npd-x = \"x.md\"\n",
+            "[aliases]\non = true\n",
+        ] {
+            fs::write(&path, content).unwrap();
+            let result = AliasRegistry::builtin().layer(&path, AliasSource::User);
+            assert!(
+                matches!(result, Err(AppError::ConfigError(ConfigError::InvalidConfigFile { .. }))),
+                "{content}"
+            );
+        }
     }
 }
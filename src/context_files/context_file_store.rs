@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use crate::context_files::TrashedFile;
 use crate::error::AppError;
 use crate::project_fs::SafePath;
 
@@ -16,6 +17,30 @@ impl ContextWriteStatus {
     }
 }
 
+/// A file under `.mx/`, as listed by [`ContextFileStore::list_context_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextFileInfo {
+    /// Path under `.mx/`.
+    pub relative_path: PathBuf,
+    pub size: u64,
+    pub lines: usize,
+    /// Seconds since the Unix epoch, when the store tracks it.
+    pub modified: Option<u64>,
+}
+
+impl ContextFileInfo {
+    pub fn new(relative_path: PathBuf, contents: &[u8], modified: Option<u64>) -> Self {
+        let newlines = contents.iter().filter(|&&byte| byte == b'\n').count();
+        let unterminated = contents.last().is_some_and(|&byte| byte != b'\n');
+        Self {
+            relative_path,
+            size: contents.len() as u64,
+            lines: newlines + usize::from(unterminated),
+            modified,
+        }
+    }
+}
+
 pub trait ContextFileStore {
     fn prepare_context_file(
         &self,
@@ -23,7 +48,17 @@ pub trait ContextFileStore {
         force: bool,
     ) -> Result<ContextWriteStatus, AppError>;
     fn write_context_contents(&self, absolute_path: &Path, contents: &str) -> Result<(), AppError>;
+    /// Adds `contents` to the end of an existing context file in a single write.
+    fn append_context_contents(&self, absolute_path: &Path, contents: &str)
+        -> Result<(), AppError>;
     fn read_context_contents(&self, relative_path: &SafePath) -> Result<String, AppError>;
+    fn context_file_exists(&self, relative_path: &SafePath) -> bool;
+    /// Every context file, sorted by path. The `.gitignore` mx keeps in `.mx/` is left out.
+    fn list_context_files(&self) -> Result<Vec<ContextFileInfo>, AppError>;
     fn remove_context_root(&self) -> Result<bool, AppError>;
     fn remove_context_file(&self, relative_path: &SafePath) -> Result<PathBuf, AppError>;
+    /// Files moved to the trash by clean or overwritten by `touch --force`, newest first.
+    fn list_trash(&self) -> Result<Vec<TrashedFile>, AppError>;
+    /// Puts the newest trashed copy of `relative_path` back, trashing the current file first.
+    fn restore_context_file(&self, relative_path: &SafePath) -> Result<TrashedFile, AppError>;
 }
@@ -1,4 +1,5 @@
-use crate::context_files::{ContextFileStore, ContextWriteStatus};
+use crate::context_files::trash::ensure_outside_trash;
+use crate::context_files::{ContextFileInfo, ContextFileStore, ContextWriteStatus, TrashedFile};
 use crate::error::AppError;
 use crate::project_fs::SafePath;
 use std::cell::RefCell;
@@ -8,6 +9,34 @@ use std::path::{Path, PathBuf};
 #[derive(Default)]
 pub struct InMemoryContextStore {
     files: RefCell<HashMap<PathBuf, String>>,
+    /// Trashed files with their contents, oldest first. Batches are numbered in order.
+    trash: RefCell<Vec<(TrashedFile, String)>>,
+    batches: RefCell<u64>,
+}
+
+impl InMemoryContextStore {
+    /// Moves the files at `paths` (under `.mx/`) into one new batch, or copies them.
+    fn trash(&self, paths: &[PathBuf], copy: bool) {
+        let mut batches = self.batches.borrow_mut();
+        *batches += 1;
+        for path in paths {
+            let contents = if copy {
+                self.files.borrow().get(path).cloned()
+            } else {
+                self.files.borrow_mut().remove(path)
+            };
+            let (Some(contents), Ok(relative_path)) = (contents, path.strip_prefix(".mx")) else {
+                continue;
+            };
+            let file = TrashedFile {
+                batch: batches.to_string(),
+                relative_path: relative_path.to_path_buf(),
+                trashed_at: *batches,
+                size: contents.len() as u64,
+            };
+            self.trash.borrow_mut().push((file, contents));
+        }
+    }
 }
 
 impl ContextFileStore for InMemoryContextStore {
@@ -16,9 +45,13 @@ impl ContextFileStore for InMemoryContextStore {
         relative_path: &SafePath,
         force: bool,
     ) -> Result<ContextWriteStatus, AppError> {
+        ensure_outside_trash(relative_path)?;
         let path = PathBuf::from(".mx").join(relative_path);
         let existed = self.files.borrow().contains_key(&path);
         let overwritten = force && existed;
+        if overwritten {
+            self.trash(std::slice::from_ref(&path), true);
+        }
 
         if !existed || force {
             self.files.borrow_mut().entry(path.clone()).or_default();
@@ -28,10 +61,21 @@ impl ContextFileStore for InMemoryContextStore {
     }
 
     fn write_context_contents(&self, absolute_path: &Path, contents: &str) -> Result<(), AppError> {
+        ensure_outside_trash(absolute_path.strip_prefix(".mx").unwrap_or(absolute_path))?;
         self.files.borrow_mut().insert(absolute_path.to_path_buf(), contents.to_string());
         Ok(())
     }
 
+    fn append_context_contents(
+        &self,
+        absolute_path: &Path,
+        contents: &str,
+    ) -> Result<(), AppError> {
+        ensure_outside_trash(absolute_path.strip_prefix(".mx").unwrap_or(absolute_path))?;
+        self.files.borrow_mut().entry(absolute_path.to_path_buf()).or_default().push_str(contents);
+        Ok(())
+    }
+
     fn read_context_contents(&self, relative_path: &SafePath) -> Result<String, AppError> {
         let path = PathBuf::from(".mx").join(relative_path);
         self.files.borrow().get(&path).cloned().ok_or_else(|| {
@@ -42,15 +86,36 @@ impl ContextFileStore for InMemoryContextStore {
         })
     }
 
+    fn context_file_exists(&self, relative_path: &SafePath) -> bool {
+        self.files.borrow().contains_key(&PathBuf::from(".mx").join(relative_path))
+    }
+
+    fn list_context_files(&self) -> Result<Vec<ContextFileInfo>, AppError> {
+        let mut files: Vec<ContextFileInfo> = self
+            .files
+            .borrow()
+            .iter()
+            .filter_map(|(path, contents)| {
+                let relative_path = path.strip_prefix(".mx").ok()?.to_path_buf();
+                Some(ContextFileInfo::new(relative_path, contents.as_bytes(), None))
+            })
+            .collect();
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        Ok(files)
+    }
+
     fn remove_context_root(&self) -> Result<bool, AppError> {
-        let had_entries = !self.files.borrow().is_empty();
-        self.files.borrow_mut().clear();
-        Ok(had_entries)
+        let paths: Vec<PathBuf> = self.files.borrow().keys().cloned().collect();
+        if !paths.is_empty() {
+            self.trash(&paths, false);
+        }
+        Ok(!paths.is_empty())
     }
 
     fn remove_context_file(&self, relative_path: &SafePath) -> Result<PathBuf, AppError> {
         let path = PathBuf::from(".mx").join(relative_path);
-        if self.files.borrow_mut().remove(&path).is_some() {
+        if self.files.borrow().contains_key(&path) {
+            self.trash(std::slice::from_ref(&path), false);
             return Ok(path);
         }
 
@@ -59,4 +124,29 @@ impl ContextFileStore for InMemoryContextStore {
             path.display()
         ))))
     }
+
+    fn list_trash(&self) -> Result<Vec<TrashedFile>, AppError> {
+        Ok(self.trash.borrow().iter().rev().map(|(file, _)| file.clone()).collect())
+    }
+
+    fn restore_context_file(&self, relative_path: &SafePath) -> Result<TrashedFile, AppError> {
+        let newest = self
+            .trash
+            .borrow()
+            .iter()
+            .rposition(|(file, _)| file.relative_path.as_path() == &**relative_path);
+        let Some(index) = newest else {
+            return Err(AppError::NotFound(crate::error::NotFoundError::ContextFile(format!(
+                "No trashed copy of {}",
+                relative_path.display()
+            ))));
+        };
+        let (file, contents) = self.trash.borrow_mut().remove(index);
+        let path = PathBuf::from(".mx").join(relative_path);
+        if self.files.borrow().contains_key(&path) {
+            self.trash(std::slice::from_ref(&path), false);
+        }
+        self.files.borrow_mut().insert(path, contents);
+        Ok(file)
+    }
 }
@@ -1,40 +1,133 @@
-use crate::context_files::alias_registry::resolve_alias;
+use crate::context_files::{AliasRegistry, AliasSource};
 use crate::error::AppError;
 use crate::project_fs::SafePath;
+use serde::Serialize;
 use std::path::{Path, PathBuf};
 
+/// One decision made while turning a context key into a path, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum ResolutionStep {
+    /// A `pd-` prefix was stripped and `pending/` prepended.
+    PendingPrefix {
+        remainder: String,
+    },
+    Alias {
+        key: String,
+        path: String,
+        source: AliasSource,
+    },
+    /// `tkN` maps to `tasks/tasksN.md`.
+    NumberedTasks {
+        number: String,
+    },
+    /// The key was used as a path as written.
+    Literal,
+    /// `.md` was appended because the key had no extension.
+    ExtensionAdded,
+}
+
+/// How [`resolve_context_path_with`] handled a key, for `mx resolve`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextResolution {
+    pub key: String,
+    pub steps: Vec<ResolutionStep>,
+    /// Path under `.mx/`.
+    pub path: PathBuf,
+}
+
+/// Resolves `key` with the built-in aliases only.
 pub fn resolve_context_path(key: &str) -> PathBuf {
+    resolve_context_path_with(key, &AliasRegistry::builtin())
+}
+
+pub fn resolve_context_path_with(key: &str, aliases: &AliasRegistry) -> PathBuf {
+    explain_context_path(key, aliases).path
+}
+
+pub fn explain_context_path(key: &str, aliases: &AliasRegistry) -> ContextResolution {
+    let mut steps = Vec::new();
     let mut current_key = key;
     let mut prefix_path = PathBuf::new();
 
     while let Some(remainder) = current_key.strip_prefix("pd-") {
         prefix_path.push("pending");
         current_key = remainder;
+        steps.push(ResolutionStep::PendingPrefix { remainder: remainder.to_string() });
     }
 
-    if let Some(mapped) = resolve_alias(current_key) {
-        return prefix_path.join(mapped);
+    let path = resolve_key(current_key, aliases, &mut steps);
+    ContextResolution { key: key.to_string(), steps, path: prefix_path.join(path) }
+}
+
+fn resolve_key(key: &str, aliases: &AliasRegistry, steps: &mut Vec<ResolutionStep>) -> PathBuf {
+    if let Some(alias) = aliases.get(key) {
+        steps.push(ResolutionStep::Alias {
+            key: alias.key.clone(),
+            path: alias.path.clone(),
+            source: alias.source,
+        });
+        return PathBuf::from(&alias.path);
     }
 
-    if let Some(remainder) = current_key.strip_prefix("tk") {
+    if let Some(remainder) = key.strip_prefix("tk") {
         if !remainder.is_empty() && remainder.chars().all(char::is_numeric) {
-            return prefix_path.join(format!("tasks/tasks{}.md", remainder));
+            steps.push(ResolutionStep::NumberedTasks { number: remainder.to_string() });
+            return PathBuf::from(format!("tasks/tasks{}.md", remainder));
         }
     }
 
-    let mut path = prefix_path.join(current_key);
+    steps.push(ResolutionStep::Literal);
+    let mut path = PathBuf::from(key);
     if path.extension().is_none() {
         let file_name = path.file_name().and_then(|segment| segment.to_str()).unwrap_or("");
         if !file_name.starts_with('.') {
             path.set_extension("md");
+            steps.push(ResolutionStep::ExtensionAdded);
         }
     }
 
     path
 }
 
+/// The key that resolves to `path` through an alias, a numbered tasks file or `pd-` prefixes,
+/// if any. Plain paths such as `docs/spec.md` have no key of their own.
+pub fn context_key_for(path: &Path, aliases: &AliasRegistry) -> Option<String> {
+    let mut prefix = String::new();
+    let mut rest = path;
+    loop {
+        let numbered = rest
+            .strip_prefix("tasks")
+            .ok()
+            .and_then(|file| file.to_str())
+            .and_then(|file| file.strip_prefix("tasks")?.strip_suffix(".md"))
+            .filter(|number| !number.is_empty() && number.chars().all(char::is_numeric))
+            .map(|number| format!("tk{number}"));
+        let found = aliases
+            .aliases()
+            .filter(|alias| Path::new(&alias.path) == rest)
+            .map(|alias| alias.key.clone())
+            .chain(numbered)
+            .map(|key| format!("{prefix}{key}"))
+            .find(|key| resolve_context_path_with(key, aliases) == path);
+        if found.is_some() {
+            return found;
+        }
+        rest = rest.strip_prefix("pending").ok().filter(|rest| !rest.as_os_str().is_empty())?;
+        prefix.push_str("pd-");
+    }
+}
+
+/// [`resolve_validated_context_path_with`] using the built-in aliases only.
 pub fn resolve_validated_context_path(key: &str) -> Result<SafePath, AppError> {
-    let resolved = resolve_context_path(key);
+    resolve_validated_context_path_with(key, &AliasRegistry::builtin())
+}
+
+pub fn resolve_validated_context_path_with(
+    key: &str,
+    aliases: &AliasRegistry,
+) -> Result<SafePath, AppError> {
+    let resolved = resolve_context_path_with(key, aliases);
     SafePath::try_from_path(Path::new(key))?;
     SafePath::try_from_path(&resolved)
 }
@@ -51,9 +144,50 @@ mod tests {
         assert_eq!(resolve_context_path("docs/spec"), PathBuf::from("docs/spec.md"));
     }
 
+    #[test]
+    fn context_key_for_reverses_aliases_numbered_tasks_and_pending() {
+        let aliases = AliasRegistry::builtin();
+        let key_for = |path: &str| context_key_for(Path::new(path), &aliases);
+        assert_eq!(key_for("tasks.md").as_deref(), Some("tk"));
+        assert_eq!(key_for("tasks/tasks3.md").as_deref(), Some("tk3"));
+        assert_eq!(key_for("pending/tasks.md").as_deref(), Some("pdt"));
+        assert_eq!(key_for("pending/error.md").as_deref(), Some("pd-er"));
+        assert_eq!(key_for("pending/pending/tasks.md").as_deref(), Some("pd-pdt"));
+        assert_eq!(key_for("docs/spec.md"), None);
+        assert_eq!(key_for("pending"), None);
+    }
+
     #[test]
     fn validated_resolver_rejects_traversal_and_accepts_safe_dots() {
         assert!(resolve_validated_context_path("../escape").is_err());
         assert!(resolve_validated_context_path("notes..v2").is_ok());
     }
+
+    #[test]
+    fn explain_records_each_resolution_step() {
+        let aliases = AliasRegistry::builtin();
+
+        let pending = explain_context_path("pd-tk3", &aliases);
+        assert_eq!(
+            pending.steps,
+            vec![
+                ResolutionStep::PendingPrefix { remainder: "tk3".to_string() },
+                ResolutionStep::NumberedTasks { number: "3".to_string() },
+            ]
+        );
+        assert_eq!(pending.path, PathBuf::from("pending/tasks/tasks3.md"));
+
+        let alias = explain_context_path("rq", &aliases);
+        assert!(matches!(
+            &alias.steps[..],
+            [ResolutionStep::Alias { source: AliasSource::Builtin, .. }]
+        ));
+
+        let literal = explain_context_path("docs/spec", &aliases);
+        assert_eq!(literal.steps, vec![ResolutionStep::Literal, ResolutionStep::ExtensionAdded]);
+        assert_eq!(
+            explain_context_path("notes.txt", &aliases).steps,
+            vec![ResolutionStep::Literal]
+        );
+    }
 }
@@ -1,27 +1,115 @@
-use crate::context_files::{ContextFileStore, ContextWriteStatus};
+use crate::context_files::trash::{batches, ensure_outside_trash, prune, TRASH_DIR};
+use crate::context_files::{
+    ContextFileInfo, ContextFileStore, ContextWriteStatus, TrashConfig, TrashedFile,
+};
 use crate::error::{AppError, NotFoundError, PathTraversalError};
-use crate::project_fs::SafePath;
+use crate::project_fs::{create_timestamped_dir, move_file, prune_empty_dirs, SafePath};
+use crate::timestamp::unix_now;
 use std::ffi::OsStr;
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 const GITIGNORE_FILE: &str = ".gitignore";
 
 #[derive(Debug, Clone)]
 pub struct LocalContextFileStore {
     workspace_root: PathBuf,
+    trash: TrashConfig,
 }
 
 impl LocalContextFileStore {
     pub fn new(workspace_root: PathBuf) -> Self {
-        Self { workspace_root }
+        Self { workspace_root, trash: TrashConfig::default() }
+    }
+
+    pub fn with_trash(mut self, trash: TrashConfig) -> Self {
+        self.trash = trash;
+        self
     }
 
     fn mx_dir(&self) -> PathBuf {
         self.workspace_root.join(".mx")
     }
+
+    fn trash_root(&self) -> PathBuf {
+        self.mx_dir().join(TRASH_DIR)
+    }
+
+    /// Moves `paths` under `.mx/` into a new trash batch, or copies them there when `copy` is
+    /// set. With the trash turned off, moved paths are deleted and copies are skipped.
+    fn trash(&self, paths: &[PathBuf], copy: bool) -> Result<(), AppError> {
+        if !self.trash.enabled() {
+            for path in paths.iter().filter(|_| !copy) {
+                if path.is_dir() {
+                    fs::remove_dir_all(path)?;
+                } else {
+                    fs::remove_file(path)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let mx_dir = self.mx_dir();
+        let batch = create_timestamped_dir(&self.trash_root())?;
+        for path in paths {
+            let target = batch.join(path.strip_prefix(&mx_dir).unwrap_or(path));
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if copy {
+                fs::copy(path, &target)?;
+            } else {
+                fs::rename(path, &target)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `absolute_path` is a context file mx may write: under `.mx/` but not in
+    /// its trash.
+    fn ensure_writable(&self, absolute_path: &Path) -> Result<(), AppError> {
+        let Ok(relative_path) = absolute_path.strip_prefix(self.mx_dir()) else {
+            return Err(AppError::PathTraversal(PathTraversalError::Detected(
+                "Invalid path. Cannot create files outside of .mx directory.".to_string(),
+            )));
+        };
+        ensure_outside_trash(relative_path)
+    }
+
+    fn prune_trash(&self) -> Result<(), AppError> {
+        prune(&self.trash_root(), &self.trash, unix_now())?;
+        Ok(())
+    }
+
+    fn collect_files(&self, dir: &Path, files: &mut Vec<ContextFileInfo>) -> Result<(), AppError> {
+        let mx_dir = self.mx_dir();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if !(dir == mx_dir && entry.file_name() == TRASH_DIR) {
+                    self.collect_files(&path, files)?;
+                }
+                continue;
+            }
+            if !file_type.is_file() || (dir == mx_dir && entry.file_name() == GITIGNORE_FILE) {
+                continue;
+            }
+            let modified = entry
+                .metadata()?
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs());
+            let relative_path = path.strip_prefix(&mx_dir).unwrap_or(&path).to_path_buf();
+            files.push(ContextFileInfo::new(relative_path, &fs::read(&path)?, modified));
+        }
+        Ok(())
+    }
 }
 
 impl ContextFileStore for LocalContextFileStore {
@@ -30,6 +118,7 @@ impl ContextFileStore for LocalContextFileStore {
         relative_path: &SafePath,
         force: bool,
     ) -> Result<ContextWriteStatus, AppError> {
+        ensure_outside_trash(relative_path)?;
         let mx_dir = self.mx_dir();
         if !mx_dir.exists() {
             fs::create_dir(&mx_dir)?;
@@ -50,6 +139,10 @@ impl ContextFileStore for LocalContextFileStore {
 
         let (existed, overwritten) = if force {
             let exists = target_path.exists();
+            if exists && target_path.is_file() {
+                self.trash(std::slice::from_ref(&target_path), true)?;
+                self.prune_trash()?;
+            }
             (exists, exists)
         } else {
             match OpenOptions::new().write(true).create_new(true).open(&target_path) {
@@ -63,15 +156,24 @@ impl ContextFileStore for LocalContextFileStore {
     }
 
     fn write_context_contents(&self, absolute_path: &Path, contents: &str) -> Result<(), AppError> {
-        if !absolute_path.starts_with(self.mx_dir()) {
-            return Err(AppError::PathTraversal(PathTraversalError::Detected(
-                "Invalid path. Cannot create files outside of .mx directory.".to_string(),
-            )));
-        }
+        self.ensure_writable(absolute_path)?;
         fs::write(absolute_path, contents)?;
         Ok(())
     }
 
+    fn append_context_contents(
+        &self,
+        absolute_path: &Path,
+        contents: &str,
+    ) -> Result<(), AppError> {
+        self.ensure_writable(absolute_path)?;
+        // One write to a file opened with O_APPEND lands at the end as a whole, so appends
+        // from parallel sessions do not interleave.
+        let mut file = OpenOptions::new().append(true).open(absolute_path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+
     fn read_context_contents(&self, relative_path: &SafePath) -> Result<String, AppError> {
         let full_path = self.mx_dir().join(relative_path);
 
@@ -97,24 +199,37 @@ impl ContextFileStore for LocalContextFileStore {
         })
     }
 
+    fn context_file_exists(&self, relative_path: &SafePath) -> bool {
+        self.mx_dir().join(relative_path).is_file()
+    }
+
+    fn list_context_files(&self) -> Result<Vec<ContextFileInfo>, AppError> {
+        let mx_dir = self.mx_dir();
+        let mut files = Vec::new();
+        if mx_dir.is_dir() {
+            self.collect_files(&mx_dir, &mut files)?;
+        }
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        Ok(files)
+    }
+
     fn remove_context_root(&self) -> Result<bool, AppError> {
         let mx_dir = self.mx_dir();
         if mx_dir.exists() {
+            let mut paths = Vec::new();
             for entry in fs::read_dir(&mx_dir)? {
                 let entry = entry?;
-                if entry.file_name() == OsStr::new(GITIGNORE_FILE) {
+                let name = entry.file_name();
+                if name == OsStr::new(GITIGNORE_FILE) || name == OsStr::new(TRASH_DIR) {
                     continue;
                 }
-
-                let file_type = entry.file_type()?;
-                let path = entry.path();
-                if file_type.is_dir() {
-                    fs::remove_dir_all(path)?;
-                } else {
-                    fs::remove_file(path)?;
-                }
+                paths.push(entry.path());
             }
 
+            if !paths.is_empty() {
+                self.trash(&paths, false)?;
+                self.prune_trash()?;
+            }
             return Ok(true);
         }
 
@@ -125,8 +240,9 @@ impl ContextFileStore for LocalContextFileStore {
         let mx_dir = self.mx_dir();
         let target_path = mx_dir.join(relative_path);
 
-        if target_path.exists() {
-            fs::remove_file(&target_path)?;
+        if target_path.exists() && !relative_path.starts_with(TRASH_DIR) {
+            self.trash(std::slice::from_ref(&target_path), false)?;
+            self.prune_trash()?;
 
             if let Some(parent) = target_path.parent() {
                 for candidate in parent.ancestors() {
@@ -147,6 +263,67 @@ impl ContextFileStore for LocalContextFileStore {
             target_path.display()
         ))))
     }
+
+    fn list_trash(&self) -> Result<Vec<TrashedFile>, AppError> {
+        let trash_root = self.trash_root();
+        let mut trashed = Vec::new();
+        for (batch, trashed_at) in batches(&trash_root)? {
+            let batch_dir = trash_root.join(&batch);
+            let mut files = Vec::new();
+            walk_files(&batch_dir, &mut files)?;
+            files.sort();
+            for path in files {
+                trashed.push(TrashedFile {
+                    batch: batch.clone(),
+                    relative_path: path.strip_prefix(&batch_dir).unwrap_or(&path).to_path_buf(),
+                    trashed_at,
+                    size: fs::metadata(&path)?.len(),
+                });
+            }
+        }
+        Ok(trashed)
+    }
+
+    fn restore_context_file(&self, relative_path: &SafePath) -> Result<TrashedFile, AppError> {
+        let trash_root = self.trash_root();
+        let found = batches(&trash_root)?
+            .into_iter()
+            .find(|(batch, _)| trash_root.join(batch).join(relative_path).is_file());
+        let Some((batch, trashed_at)) = found else {
+            return Err(AppError::NotFound(NotFoundError::ContextFile(format!(
+                "No trashed copy of {}",
+                relative_path.display()
+            ))));
+        };
+        let source = trash_root.join(&batch).join(relative_path);
+        let size = fs::metadata(&source)?.len();
+
+        // The current file goes to the trash first so the restore can be undone too. Pruning
+        // waits until the restored copy is out of its batch.
+        let target = self.mx_dir().join(relative_path);
+        if target.is_file() {
+            self.trash(std::slice::from_ref(&target), false)?;
+        }
+        move_file(&source, &target)?;
+        if let Some(parent) = source.parent() {
+            prune_empty_dirs(parent, &trash_root);
+        }
+        self.prune_trash()?;
+
+        Ok(TrashedFile { batch, relative_path: relative_path.to_path_buf(), trashed_at, size })
+    }
+}
+
+fn walk_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            walk_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -183,6 +360,102 @@ mod tests {
         assert!(matches!(result, Err(AppError::PathTraversal(PathTraversalError::Detected(_)))));
     }
 
+    #[test]
+    fn adapter_rejects_writes_into_the_trash() {
+        let workspace = tempdir().unwrap();
+        let store = LocalContextFileStore::new(workspace.path().to_path_buf());
+        let trashed = SafePath::try_from_path(Path::new(".trash/1700000000/tasks.md")).unwrap();
+
+        let result = store.prepare_context_file(&trashed, true);
+        assert!(matches!(result, Err(AppError::InvalidKey(_))));
+        assert!(!workspace.path().join(".mx").exists());
+
+        let inside = workspace.path().join(".mx/.trash/1700000000/tasks.md");
+        assert!(store.write_context_contents(&inside, "x").is_err());
+        assert!(store.append_context_contents(&inside, "x").is_err());
+        assert!(!inside.exists());
+    }
+
+    #[test]
+    fn append_adds_to_the_end_of_an_existing_file() {
+        let workspace = tempdir().unwrap();
+        let store = LocalContextFileStore::new(workspace.path().to_path_buf());
+        let status = store
+            .prepare_context_file(&SafePath::try_from_path(Path::new("error.md")).unwrap(), false)
+            .unwrap();
+        store.write_context_contents(&status.path, "first\n").unwrap();
+
+        store.append_context_contents(&status.path, "second\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&status.path).unwrap(), "first\nsecond\n");
+        let outside = workspace.path().join("outside.md");
+        assert!(store.append_context_contents(&outside, "x").is_err());
+    }
+
+    #[test]
+    fn list_context_files_walks_subdirectories_without_the_gitignore() {
+        let workspace = tempdir().unwrap();
+        let store = LocalContextFileStore::new(workspace.path().to_path_buf());
+        assert!(store.list_context_files().unwrap().is_empty());
+        let mx_dir = workspace.path().join(".mx");
+        fs::create_dir_all(mx_dir.join("pending")).unwrap();
+        fs::write(mx_dir.join(".gitignore"), "*").unwrap();
+        fs::write(mx_dir.join("tasks.md"), "one\ntwo").unwrap();
+        fs::write(mx_dir.join("pending/tasks.md"), "").unwrap();
+
+        let files = store.list_context_files().unwrap();
+
+        let summary: Vec<(&Path, u64, usize)> = files
+            .iter()
+            .map(|file| (file.relative_path.as_path(), file.size, file.lines))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(Path::new("pending/tasks.md"), 0, 0), (Path::new("tasks.md"), 7, 2)]
+        );
+        assert!(files.iter().all(|file| file.modified.is_some()));
+    }
+
+    #[test]
+    fn clean_and_overwrite_go_to_the_trash_and_restore_from_it() {
+        let workspace = tempdir().unwrap();
+        let store = LocalContextFileStore::new(workspace.path().to_path_buf());
+        let path = SafePath::try_from_path(Path::new("docs/spec.md")).unwrap();
+        let status = store.prepare_context_file(&path, false).unwrap();
+        store.write_context_contents(&status.path, "v1").unwrap();
+        let status = store.prepare_context_file(&path, true).unwrap();
+        store.write_context_contents(&status.path, "v2").unwrap();
+
+        assert!(store.remove_context_root().unwrap());
+
+        let mx_dir = workspace.path().join(".mx");
+        assert!(!mx_dir.join("docs").exists());
+        assert!(store.list_context_files().unwrap().is_empty());
+        let trashed = store.list_trash().unwrap();
+        assert_eq!(trashed.len(), 2);
+        assert!(trashed.iter().all(|file| file.relative_path == Path::new("docs/spec.md")));
+
+        store.restore_context_file(&path).unwrap();
+        assert_eq!(fs::read_to_string(mx_dir.join("docs/spec.md")).unwrap(), "v2");
+        store.remove_context_root().unwrap();
+        assert!(mx_dir.join(TRASH_DIR).is_dir());
+    }
+
+    #[test]
+    fn disabled_trash_deletes_outright() {
+        let workspace = tempdir().unwrap();
+        let store = LocalContextFileStore::new(workspace.path().to_path_buf())
+            .with_trash(TrashConfig { max_batches: 0, max_age_days: None });
+        let path = SafePath::try_from_path(Path::new("tasks.md")).unwrap();
+        let status = store.prepare_context_file(&path, false).unwrap();
+        store.write_context_contents(&status.path, "task").unwrap();
+
+        store.remove_context_file(&path).unwrap();
+
+        assert!(store.list_trash().unwrap().is_empty());
+        assert!(!workspace.path().join(".mx").join(TRASH_DIR).exists());
+    }
+
     #[test]
     fn remove_context_root_preserves_gitignore() {
         let workspace = tempdir().unwrap();
@@ -2,12 +2,19 @@ mod alias_registry;
 mod context_file_store;
 mod key;
 mod local_context_store;
+mod trash;
 
 #[cfg(test)]
 mod in_memory_context_store;
 #[cfg(test)]
 pub use in_memory_context_store::InMemoryContextStore;
 
-pub use context_file_store::{ContextFileStore, ContextWriteStatus};
-pub use key::{resolve_context_path, resolve_validated_context_path};
+pub use alias_registry::{AliasRegistry, AliasSource, ContextAlias, PROJECT_CONFIG_FILE};
+pub use context_file_store::{ContextFileInfo, ContextFileStore, ContextWriteStatus};
+pub use key::{
+    context_key_for, explain_context_path, resolve_context_path, resolve_context_path_with,
+    resolve_validated_context_path, resolve_validated_context_path_with, ContextResolution,
+    ResolutionStep,
+};
 pub use local_context_store::LocalContextFileStore;
+pub use trash::{TrashConfig, TrashedFile, TRASH_DIR};
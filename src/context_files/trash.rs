@@ -0,0 +1,127 @@
+use crate::error::{AppError, InvalidKeyError};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directory under `.mx/` that holds removed and overwritten context files, one
+/// `<unix-seconds>` batch per operation. It is never cleaned, listed or enumerated itself.
+pub const TRASH_DIR: &str = ".trash";
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How long trashed context files are kept, from the `trash` section of the user config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashConfig {
+    /// Batches kept, newest first. `0` turns the trash off and deletes outright.
+    pub max_batches: usize,
+    pub max_age_days: Option<u64>,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { max_batches: 20, max_age_days: Some(30) }
+    }
+}
+
+impl TrashConfig {
+    pub fn enabled(&self) -> bool {
+        self.max_batches > 0
+    }
+}
+
+/// A context file in the trash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedFile {
+    /// Name of the batch directory under `.mx/.trash/`.
+    pub batch: String,
+    /// Path under `.mx/` the file had, and is restored to.
+    pub relative_path: PathBuf,
+    /// Seconds since the Unix epoch.
+    pub trashed_at: u64,
+    pub size: u64,
+}
+
+/// Refuses a path under `.mx/` that lies inside the trash. Trashed files only come back
+/// through `mx restore --context`, so nothing may be written there directly.
+pub fn ensure_outside_trash(relative_path: &Path) -> Result<(), AppError> {
+    if relative_path.starts_with(TRASH_DIR) {
+        return Err(AppError::InvalidKey(InvalidKeyError::Other(format!(
+            "'{}' is inside .mx/{TRASH_DIR}; use `mx restore --context` to bring a file back",
+            relative_path.display()
+        ))));
+    }
+    Ok(())
+}
+
+/// The time a batch was created, from its `<seconds>` or `<seconds>-<n>` name.
+pub fn batch_time(batch: &str) -> Option<u64> {
+    batch.split('-').next()?.parse().ok()
+}
+
+/// Batch directories under `root`, newest first.
+pub fn batches(root: &Path) -> io::Result<Vec<(String, u64)>> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut batches = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let (true, Some(time)) = (entry.file_type()?.is_dir(), batch_time(&name)) {
+            batches.push((name, time));
+        }
+    }
+    batches.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| batch_suffix(&b.0).cmp(&batch_suffix(&a.0))));
+    Ok(batches)
+}
+
+fn batch_suffix(batch: &str) -> u64 {
+    batch.split_once('-').and_then(|(_, suffix)| suffix.parse().ok()).unwrap_or(0)
+}
+
+/// Deletes the batches under `root` beyond `config.max_batches` or older than
+/// `config.max_age_days`.
+pub fn prune(root: &Path, config: &TrashConfig, now: u64) -> io::Result<()> {
+    let cutoff = config.max_age_days.map(|days| now.saturating_sub(days * SECONDS_PER_DAY));
+    for (index, (name, time)) in batches(root)?.into_iter().enumerate() {
+        if index >= config.max_batches || cutoff.is_some_and(|cutoff| time < cutoff) {
+            fs::remove_dir_all(root.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn prune_keeps_the_newest_batches_within_the_age_limit() {
+        let root = tempdir().unwrap();
+        for name in ["100", "200", "300", "300-1", "notes"] {
+            fs::create_dir(root.path().join(name)).unwrap();
+        }
+        assert_eq!(
+            batches(root.path()).unwrap(),
+            vec![
+                ("300-1".to_string(), 300),
+                ("300".to_string(), 300),
+                ("200".to_string(), 200),
+                ("100".to_string(), 100)
+            ]
+        );
+
+        let config = TrashConfig { max_batches: 3, max_age_days: Some(1) };
+        prune(root.path(), &config, 200 + SECONDS_PER_DAY).unwrap();
+
+        let left: Vec<String> =
+            batches(root.path()).unwrap().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(left, vec!["300-1", "300", "200"]);
+        assert!(root.path().join("notes").is_dir());
+    }
+}
@@ -19,6 +19,12 @@ pub enum AppError {
 
     #[error(transparent)]
     PathTraversal(#[from] PathTraversalError),
+
+    #[error("Lint found {errors} error(s), {warnings} warning(s)")]
+    LintFailed { errors: usize, warnings: usize },
+
+    #[error("Failed to run '{command}': {source}")]
+    CommandFailed { command: String, source: io::Error },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -35,6 +41,14 @@ pub enum ConfigError {
     RelativePathDerivation(String),
     #[error("File system error: {0}")]
     Io(String),
+    #[error("Invalid config file {path}: {message}")]
+    InvalidConfigFile { path: String, message: String },
+    #[error("Invalid snippet pack: {0}")]
+    InvalidPack(String),
+    #[error("Failed to sync source '{name}': {message}")]
+    SourceSync { name: String, message: String },
+    #[error("Missing required inputs: {0}")]
+    MissingRequirements(String),
     #[error("Configuration error: {0}")]
     Other(String),
 }
@@ -94,6 +108,8 @@ impl AppError {
             Self::ClipboardError(_) => io::ErrorKind::Other,
             Self::InvalidKey(_) => io::ErrorKind::InvalidInput,
             Self::PathTraversal(_) => io::ErrorKind::InvalidInput,
+            Self::LintFailed { .. } => io::ErrorKind::InvalidData,
+            Self::CommandFailed { source, .. } => source.kind(),
         }
     }
 }
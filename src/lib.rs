@@ -1,14 +1,18 @@
 pub mod app;
 pub mod cli;
 pub mod clipboard;
+pub mod config;
 pub mod context_files;
 pub mod error;
 pub mod project_fs;
 pub mod snippets;
+pub mod sources;
+pub mod timestamp;
 
 pub use app::{
-    cat_context, clean_context, copy_snippet, list_snippets, touch_context, which_path,
-    CleanOutcome, CopyOutcome, ListEntry, TouchOutcome, WhichOutcome,
+    cat_context, clean_context, copy_snippet, list_snippets, new_snippet, search_snippets,
+    touch_context, which_path, CleanOutcome, CopyOutcome, ListEntry, NewOutcome, NewSnippetOptions,
+    SearchHit, SearchOptions, SnippetSource, TouchOutcome, WhichOutcome,
 };
 pub use cli::run as cli;
 pub use error::AppError;
@@ -0,0 +1,80 @@
+use crate::timestamp::unix_now;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Creates a fresh `<root>/<unix-seconds>` directory, adding a numeric suffix when a batch
+/// for the same second already exists.
+pub fn create_timestamped_dir(root: &Path) -> io::Result<PathBuf> {
+    let seconds = unix_now();
+    fs::create_dir_all(root)?;
+
+    let mut candidate = root.join(seconds.to_string());
+    let mut suffix = 1;
+    loop {
+        match fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                candidate = root.join(format!("{seconds}-{suffix}"));
+                suffix += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Moves `from` to `to`, creating parent directories and falling back to copy-and-delete when
+/// a rename is not possible (for example across file systems).
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+/// Removes `start` and its ancestors while they are empty, stopping before `root`.
+pub fn prune_empty_dirs(start: &Path, root: &Path) {
+    for candidate in start.ancestors() {
+        if !candidate.starts_with(root) || candidate == root {
+            break;
+        }
+        if fs::remove_dir(candidate).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn create_timestamped_dir_never_reuses_a_batch() {
+        let root = tempdir().unwrap();
+        let first = create_timestamped_dir(root.path()).unwrap();
+        let second = create_timestamped_dir(root.path()).unwrap();
+        assert_ne!(first, second);
+        assert!(first.is_dir() && second.is_dir());
+    }
+
+    #[test]
+    fn move_file_and_prune_empty_dirs_clean_up_source_tree() {
+        let root = tempdir().unwrap();
+        let source = root.path().join("a/b/file.md");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "body").unwrap();
+        let target = root.path().join("c/file.md");
+
+        move_file(&source, &target).unwrap();
+        prune_empty_dirs(source.parent().unwrap(), root.path());
+
+        assert_eq!(fs::read_to_string(target).unwrap(), "body");
+        assert!(!root.path().join("a").exists());
+        assert!(root.path().exists());
+    }
+}
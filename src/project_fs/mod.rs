@@ -1,4 +1,5 @@
 mod current_directory_locator;
+mod file_moves;
 mod safe_path;
 mod workspace_file_reader;
 mod workspace_locator;
@@ -13,6 +14,7 @@ pub use fixed_workspace_locator::FixedWorkspaceLocator;
 pub use in_memory_workspace_file_reader::InMemoryWorkspaceFileReader;
 
 pub use current_directory_locator::CurrentDirectoryLocator;
+pub use file_moves::{create_timestamped_dir, move_file, prune_empty_dirs};
 pub use safe_path::SafePath;
 pub use workspace_file_reader::{LocalWorkspaceFileReader, WorkspaceFileReader};
 pub use workspace_locator::WorkspaceLocator;
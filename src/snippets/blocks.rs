@@ -0,0 +1,180 @@
+use super::placeholders::{classify_placeholder, Placeholder};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BlockError {
+    #[error("block '{0}' is never closed with {{{{/block}}}}")]
+    Unclosed(String),
+    #[error("{{{{/block}}}} without an open block")]
+    UnexpectedEnd,
+    #[error("block name is empty")]
+    EmptyName,
+    #[error("block '{0}' is defined more than once")]
+    Duplicate(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Block { name: &'a str, body: &'a str },
+}
+
+/// The top-level blocks of a snippet that extends another, by name.
+pub fn block_overrides(content: &str) -> Result<BTreeMap<String, String>, BlockError> {
+    let mut overrides = BTreeMap::new();
+    for segment in parse_segments(content)? {
+        if let Segment::Block { name, body } = segment {
+            if overrides.insert(name.to_string(), body.to_string()).is_some() {
+                return Err(BlockError::Duplicate(name.to_string()));
+            }
+        }
+    }
+    Ok(overrides)
+}
+
+/// Renders `template`, replacing each block named in `overrides` and keeping the default body
+/// of the others. Block markers are removed; a block overridden with content that contains the
+/// same block name renders that inner block's own body instead of recursing.
+pub fn render_blocks(
+    template: &str,
+    overrides: &BTreeMap<String, String>,
+) -> Result<String, BlockError> {
+    let mut output = String::with_capacity(template.len());
+    render_into(template, overrides, &mut Vec::new(), &mut output)?;
+    Ok(output)
+}
+
+fn render_into<'a>(
+    content: &'a str,
+    overrides: &'a BTreeMap<String, String>,
+    active: &mut Vec<&'a str>,
+    output: &mut String,
+) -> Result<(), BlockError> {
+    for segment in parse_segments(content)? {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Block { name, body } => {
+                let chosen = match overrides.get(name) {
+                    Some(replacement) if !active.contains(&name) => replacement.as_str(),
+                    _ => body,
+                };
+                active.push(name);
+                render_into(chosen, overrides, active, output)?;
+                active.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits `content` into text and top-level blocks. Nested blocks stay inside their parent's
+/// body. A marker alone on its line takes its line break with it, so blocks do not leave
+/// blank lines behind.
+fn parse_segments(content: &str) -> Result<Vec<Segment<'_>>, BlockError> {
+    let mut segments = Vec::new();
+    let mut open: Option<(&str, usize)> = None;
+    let mut depth = 0usize;
+    let mut text_start = 0;
+    let mut cursor = 0;
+
+    while let Some(found) = content[cursor..].find("{{") {
+        let start = cursor + found;
+        let inner = &content[start + 2..];
+        // Same splitting rules as placeholder expansion: a `{{` before the closing `}}`
+        // leaves the earlier one unclosed.
+        let close = match (inner.find("}}"), inner.find("{{")) {
+            (Some(close), Some(next)) if next < close => None,
+            (close, _) => close,
+        };
+        let Some(close) = close else {
+            cursor = start + 2;
+            continue;
+        };
+        let end = skip_line_break(content, start + 2 + close + 2);
+        cursor = end;
+
+        match classify_placeholder(&inner[..close]) {
+            Placeholder::BlockStart("") => return Err(BlockError::EmptyName),
+            Placeholder::BlockStart(name) => {
+                if depth == 0 {
+                    segments.push(Segment::Text(&content[text_start..start]));
+                    open = Some((name, end));
+                }
+                depth += 1;
+            }
+            Placeholder::BlockEnd => {
+                depth = depth.checked_sub(1).ok_or(BlockError::UnexpectedEnd)?;
+                if depth == 0 {
+                    if let Some((name, body_start)) = open.take() {
+                        segments.push(Segment::Block { name, body: &content[body_start..start] });
+                    }
+                    text_start = end;
+                }
+            }
+            Placeholder::SnippetInclude(_) | Placeholder::WorkspaceFile(_) => {}
+        }
+    }
+
+    if let Some((name, _)) = open {
+        return Err(BlockError::Unclosed(name.to_string()));
+    }
+    segments.push(Segment::Text(&content[text_start..]));
+    Ok(segments)
+}
+
+fn skip_line_break(content: &str, index: usize) -> usize {
+    let rest = &content[index..];
+    if rest.starts_with("\r\n") {
+        index + 2
+    } else if rest.starts_with('\n') {
+        index + 1
+    } else {
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "Review the diff.\n{{#block checklist}}\n- generic\n{{/block}}\nThanks.\n";
+
+    #[test]
+    fn render_keeps_default_bodies_and_drops_markers() {
+        let rendered = render_blocks(BASE, &BTreeMap::new()).unwrap();
+        assert_eq!(rendered, "Review the diff.\n- generic\nThanks.\n");
+    }
+
+    #[test]
+    fn render_replaces_overridden_blocks_including_nested_ones() {
+        let child =
+            "ignored\n{{#block checklist}}\n- injection\n{{#block extra}}{{/block}}{{/block}}\n";
+        let mut overrides = block_overrides(child).unwrap();
+        assert_eq!(overrides.len(), 1);
+        overrides.insert("extra".to_string(), "- secrets\n".to_string());
+
+        let rendered = render_blocks(BASE, &overrides).unwrap();
+
+        assert_eq!(rendered, "Review the diff.\n- injection\n- secrets\nThanks.\n");
+    }
+
+    #[test]
+    fn parse_rejects_unbalanced_and_duplicate_blocks() {
+        assert_eq!(
+            render_blocks("{{#block a}}x", &BTreeMap::new()),
+            Err(BlockError::Unclosed("a".to_string()))
+        );
+        assert_eq!(render_blocks("x{{/block}}", &BTreeMap::new()), Err(BlockError::UnexpectedEnd));
+        assert_eq!(
+            block_overrides("{{#block a}}1{{/block}}{{#block a}}2{{/block}}"),
+            Err(BlockError::Duplicate("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn self_referencing_override_renders_the_inner_default() {
+        let overrides =
+            BTreeMap::from([("a".to_string(), "[{{#block a}}inner{{/block}}]".to_string())]);
+        assert_eq!(render_blocks("{{#block a}}x{{/block}}", &overrides).unwrap(), "[inner]");
+    }
+}
@@ -1,98 +1,398 @@
+use crate::config::{config_dir, UserConfig};
 use crate::error::{AppError, ConfigError, NotFoundError};
 use crate::snippets::{
-    candidate_key, normalize_query, path_to_string, SnippetCatalog, SnippetEntry,
+    candidate_key, normalize_query, path_to_string, preferred_languages, read_snippet_header,
+    variant_language, variant_path, CatalogProblem, QueryMatching, SnippetCatalog, SnippetEntry,
+    UsageLog, UsageStats,
 };
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::Deserialize;
+use std::collections::HashSet;
 use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+const IGNORE_FILE: &str = ".mxignore";
+
+/// How the catalog walks the commands root, read from the `catalog` section of the user config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogConfig {
+    /// Descend into symlinked directories and read symlinked files. Link cycles are skipped.
+    /// Off unless enabled, so a plain commands root is walked as before.
+    pub follow_symlinks: bool,
+    pub matching: QueryMatching,
+}
+
+/// A synced source checkout whose snippets appear under `<namespace>/` in the catalog.
+#[derive(Debug, Clone)]
+struct SourceMount {
+    namespace: String,
+    root: PathBuf,
+}
+
 #[derive(Debug, Clone)]
 pub struct FilesystemSnippetCatalog {
     commands_root: PathBuf,
+    mounts: Vec<SourceMount>,
+    /// Usage used to settle names shared by several snippets, when enabled.
+    ranking: Option<UsageStats>,
+    follow_symlinks: bool,
+    matching: QueryMatching,
+    /// Preferred languages for snippet variants, most preferred first.
+    languages: Vec<String>,
 }
 
 impl FilesystemSnippetCatalog {
+    /// Builds the catalog for `MX_COMMANDS_ROOT` (or `~/.config/mx/commands`) and mounts every
+    /// source from the user config that has been synced.
     pub fn from_env() -> Result<Self, AppError> {
-        if let Ok(custom) = env::var("MX_COMMANDS_ROOT") {
-            let custom_path = PathBuf::from(custom);
-            let legacy_commands_root = custom_path.join("commands");
-            let commands_root =
-                if legacy_commands_root.is_dir() { legacy_commands_root } else { custom_path };
-            return Ok(Self { commands_root });
+        let mut catalog = match env::var("MX_COMMANDS_ROOT") {
+            Ok(custom) => Self::new(commands_dir(PathBuf::from(custom))),
+            Err(_) => Self::from_root(config_dir()?)?,
+        };
+
+        let config = UserConfig::from_env_or_default()?;
+        catalog.follow_symlinks = config.catalog.follow_symlinks;
+        catalog.matching = config.catalog.matching;
+        catalog.languages = preferred_languages(None);
+        if config.usage.enabled && config.usage.resolve_ambiguous {
+            // A missing or damaged usage log only costs the tie-break, not snippet resolution.
+            catalog.ranking =
+                catalog.usage_path().ok().and_then(|path| UsageLog::new(path).stats().ok());
         }
 
-        let home = env::var("HOME")
-            .map_err(|_| AppError::ConfigError(ConfigError::MissingEnvVar("HOME".to_string())))?;
-        let root = PathBuf::from(home).join(".config").join("mx");
-        Self::from_root(root)
+        if config.sources.is_empty() {
+            return Ok(catalog);
+        }
+        let sources_root = catalog.sources_root()?;
+        for source in config.sources {
+            let checkout = sources_root.join(&source.name);
+            if checkout.is_dir() {
+                catalog
+                    .mounts
+                    .push(SourceMount { namespace: source.name, root: commands_dir(checkout) });
+            }
+        }
+        Ok(catalog)
     }
 
     pub fn from_root<P: AsRef<Path>>(root: P) -> Result<Self, AppError> {
-        Ok(Self { commands_root: root.as_ref().join("commands") })
+        Ok(Self::new(root.as_ref().join("commands")))
+    }
+
+    fn new(commands_root: PathBuf) -> Self {
+        Self {
+            commands_root,
+            mounts: Vec::new(),
+            ranking: None,
+            follow_symlinks: CatalogConfig::default().follow_symlinks,
+            matching: QueryMatching::default(),
+            languages: Vec::new(),
+        }
+    }
+
+    /// Picks `languages` variants of resolved snippets, most preferred first, instead of the
+    /// `MX_LANG` or system locale preference.
+    pub fn with_languages(mut self, languages: Vec<String>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    pub fn with_matching(mut self, matching: QueryMatching) -> Self {
+        self.matching = matching;
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Resolves a name shared by several snippets to the one with the highest frecency in
+    /// `stats`, as long as it is strictly ahead of the others.
+    pub fn with_ranking(mut self, stats: UsageStats) -> Self {
+        self.ranking = Some(stats);
+        self
     }
 
     pub fn commands_root(&self) -> &Path {
         &self.commands_root
     }
 
+    /// The commands root followed by the root of every mounted source.
+    pub fn roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.commands_root.clone())
+            .chain(self.mounts.iter().map(|mount| mount.root.clone()))
+            .collect()
+    }
+
+    /// Directory that holds mx-managed snippet state (history, trash, usage log, source
+    /// checkouts): `~/.config/mx`. With the default commands root this is the directory the
+    /// commands root sits in; a custom `MX_COMMANDS_ROOT` keeps its parent free of mx files.
+    /// Only commands that keep state need `HOME`.
+    pub fn state_root(&self) -> Result<PathBuf, AppError> {
+        config_dir()
+    }
+
+    pub fn trash_root(&self) -> Result<PathBuf, AppError> {
+        Ok(self.state_root()?.join("trash"))
+    }
+
+    pub fn history_root(&self) -> Result<PathBuf, AppError> {
+        Ok(self.state_root()?.join("history"))
+    }
+
+    /// Log of snippet copies used for `mx stats` and frecency ranking.
+    pub fn usage_path(&self) -> Result<PathBuf, AppError> {
+        Ok(self.state_root()?.join("usage.json"))
+    }
+
+    /// Cache of git source checkouts, one directory per source name.
+    pub fn sources_root(&self) -> Result<PathBuf, AppError> {
+        Ok(self.state_root()?.join("sources"))
+    }
+
+    /// Every snippet file under the commands root and the mounted sources, sorted by path.
+    /// Fails on the first file or directory that cannot be walked.
+    fn walk(&self) -> Result<Vec<SnippetEntry>, AppError> {
+        let (files, problems) = self.walk_leniently();
+        match problems.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(files),
+        }
+    }
+
+    /// Like [`Self::walk`], but returns what could be walked together with every problem met
+    /// on the way, by path.
+    fn walk_leniently(&self) -> (Vec<SnippetEntry>, Vec<(String, AppError)>) {
+        let mut files = Vec::new();
+        let mut problems = Vec::new();
+        walk_snippets(&self.commands_root, None, self.follow_symlinks, &mut files, &mut problems);
+        for mount in &self.mounts {
+            walk_snippets(
+                &mount.root,
+                Some(&mount.namespace),
+                self.follow_symlinks,
+                &mut files,
+                &mut problems,
+            );
+        }
+
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        (files, problems)
+    }
+
+    fn preferred_match(&self, matches: &[SnippetEntry]) -> Option<usize> {
+        let stats = self.ranking.as_ref()?;
+        let mut scores: Vec<(u64, usize)> = matches
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (stats.frecency(&entry.relative_path), index))
+            .collect();
+        scores.sort_by(|a, b| b.0.cmp(&a.0));
+        match scores.as_slice() {
+            [(best, index), (runner_up, _), ..] if best > runner_up => Some(*index),
+            _ => None,
+        }
+    }
+
     fn join_paths(snippets: &[SnippetEntry]) -> String {
         snippets.iter().map(|snippet| snippet.relative_path.clone()).collect::<Vec<_>>().join(", ")
     }
 }
 
-impl SnippetCatalog for FilesystemSnippetCatalog {
-    fn enumerate_snippets(&self) -> Result<Vec<SnippetEntry>, AppError> {
-        if !self.commands_root.exists() {
-            return Ok(Vec::new());
-        }
+/// Accepts both a directory of snippets and a legacy root with a `commands/` subdirectory.
+fn commands_dir(root: PathBuf) -> PathBuf {
+    let legacy_commands_root = root.join("commands");
+    if legacy_commands_root.is_dir() {
+        legacy_commands_root
+    } else {
+        root
+    }
+}
 
-        let mut files = Vec::new();
-        for entry in WalkDir::new(&self.commands_root) {
-            let entry =
-                entry.map_err(|err| AppError::ConfigError(ConfigError::Io(err.to_string())))?;
-            if !entry.file_type().is_file() {
-                continue;
+/// Patterns in gitignore syntax, read from `.mxignore` at the root of the commands root or of
+/// a synced source.
+/// The `.mxignore` rules of `root`, and the problem with the file when it is malformed. The
+/// valid lines of a malformed file still apply.
+fn load_ignore(root: &Path) -> (Gitignore, Option<AppError>) {
+    let path = root.join(IGNORE_FILE);
+    let mut builder = GitignoreBuilder::new(root);
+    let mut problem = None;
+    if path.is_file() {
+        problem = builder.add(&path).map(|err| invalid_ignore(&path, err));
+    }
+    match builder.build() {
+        Ok(ignore) => (ignore, problem),
+        Err(err) => (Gitignore::empty(), Some(invalid_ignore(&path, err))),
+    }
+}
+
+fn invalid_ignore(path: &Path, err: ignore::Error) -> AppError {
+    AppError::ConfigError(ConfigError::InvalidConfigFile {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })
+}
+
+/// Snippets marked `hidden: true` stay out of listings and key lookups; they are meant to be
+/// included by other snippets. Unreadable metadata counts as visible so `mx list` reports it.
+fn is_hidden(entry: &SnippetEntry) -> bool {
+    read_snippet_header(&entry.absolute_path)
+        .ok()
+        .flatten()
+        .and_then(|metadata| metadata.hidden)
+        .unwrap_or(false)
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+/// Keeps one entry per physical file, the first by path, when links make a file reachable
+/// more than once.
+fn dedupe_linked_files(entries: Vec<SnippetEntry>) -> Vec<SnippetEntry> {
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter(|entry| match fs::canonicalize(&entry.absolute_path) {
+            Ok(physical) => seen.insert(physical),
+            Err(_) => true,
+        })
+        .collect()
+}
+
+fn walk_snippets(
+    root: &Path,
+    namespace: Option<&str>,
+    follow_symlinks: bool,
+    files: &mut Vec<SnippetEntry>,
+    problems: &mut Vec<(String, AppError)>,
+) {
+    if !root.exists() {
+        return;
+    }
+
+    // Dot-directories such as `.git` or `.github` hold repository tooling, not snippets.
+    let (ignore, problem) = load_ignore(root);
+    if let Some(err) = problem {
+        problems.push((root.join(IGNORE_FILE).display().to_string(), err));
+    }
+    let walker =
+        WalkDir::new(root).follow_links(follow_symlinks).into_iter().filter_entry(|entry| {
+            let is_dir = entry.file_type().is_dir();
+            let dot_dir = is_dir && entry.file_name().to_string_lossy().starts_with('.');
+            entry.depth() == 0 || !(dot_dir || ignore.matched(entry.path(), is_dir).is_ignore())
+        });
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // A link back to one of its own ancestors, or a link to nothing: neither holds
+            // snippets of its own.
+            Err(err) if err.loop_ancestor().is_some() || err.path().is_some_and(is_symlink) => {
+                continue
             }
-            if entry.path().extension().and_then(|ext| ext.to_str()) != Some("md") {
+            Err(err) => {
+                let path = err.path().unwrap_or(root).display().to_string();
+                problems.push((path, AppError::ConfigError(ConfigError::Io(err.to_string()))));
                 continue;
             }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if entry.path().extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
 
-            let path = entry.path();
-            let relative = path.strip_prefix(&self.commands_root).map_err(|_| {
-                AppError::ConfigError(ConfigError::RelativePathDerivation(
-                    path.display().to_string(),
-                ))
-            })?;
-            let relative_without_ext = relative.with_extension("");
-            let relative_path = path_to_string(&relative_without_ext)?;
-            let key = path
-                .file_stem()
-                .and_then(|stem| stem.to_str())
-                .ok_or(AppError::ConfigError(ConfigError::InvalidUtf8))?
-                .to_string();
-
-            files.push(SnippetEntry { key, relative_path, absolute_path: entry.into_path() });
+        // `wc.ja.md` next to `wc.md` is the Japanese variant of `wc`, not a snippet of its own.
+        if variant_language(entry.path()).is_some() {
+            continue;
         }
+        match snippet_entry(root, namespace, entry.path()) {
+            Ok(snippet) => files.push(snippet),
+            Err(err) => problems.push((entry.path().display().to_string(), err)),
+        }
+    }
+}
 
-        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+fn snippet_entry(
+    root: &Path,
+    namespace: Option<&str>,
+    path: &Path,
+) -> Result<SnippetEntry, AppError> {
+    let relative = path.strip_prefix(root).map_err(|_| {
+        AppError::ConfigError(ConfigError::RelativePathDerivation(path.display().to_string()))
+    })?;
+    let relative_without_ext = relative.with_extension("");
+    let mut relative_path = path_to_string(&relative_without_ext)?;
+    if let Some(namespace) = namespace {
+        relative_path = format!("{namespace}/{relative_path}");
+    }
+    let key = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or(AppError::ConfigError(ConfigError::InvalidUtf8))?
+        .to_string();
+
+    Ok(SnippetEntry { key, relative_path, absolute_path: path.to_path_buf() })
+}
+
+impl SnippetCatalog for FilesystemSnippetCatalog {
+    fn enumerate_snippets(&self) -> Result<Vec<SnippetEntry>, AppError> {
+        let mut files = self.enumerate_all_snippets()?;
+        files.retain(|entry| !is_hidden(entry));
         Ok(files)
     }
 
+    fn enumerate_all_snippets(&self) -> Result<Vec<SnippetEntry>, AppError> {
+        Ok(dedupe_linked_files(self.walk()?))
+    }
+
+    fn enumerate_snippets_leniently(
+        &self,
+    ) -> Result<(Vec<SnippetEntry>, Vec<CatalogProblem>), AppError> {
+        let (files, problems) = self.walk_leniently();
+        let mut files = dedupe_linked_files(files);
+        files.retain(|entry| !is_hidden(entry));
+        let problems = problems
+            .into_iter()
+            .map(|(path, err)| CatalogProblem { path, message: err.to_string() })
+            .collect();
+        Ok((files, problems))
+    }
+
+    fn localize(&self, entry: SnippetEntry) -> SnippetEntry {
+        let variant = self
+            .languages
+            .iter()
+            .map(|language| variant_path(&entry.absolute_path, language))
+            .find(|path| path.is_file());
+        let absolute_path = variant.unwrap_or(entry.absolute_path);
+        SnippetEntry { absolute_path, ..entry }
+    }
+
     fn resolve_snippet(&self, raw_query: &str) -> Result<SnippetEntry, AppError> {
         let normalized = normalize_query(raw_query)?;
         let normalized_str = normalized.to_string();
-        let query_key = candidate_key(&normalized_str);
-
-        let mut exact_matches = Vec::new();
-        let mut key_matches = Vec::new();
-
-        for snippet in self.enumerate_snippets()? {
-            if snippet.relative_path == normalized_str {
-                exact_matches.push(snippet);
-            } else if snippet.key == normalized_str || snippet.key == query_key {
-                key_matches.push(snippet);
-            }
+        let matching = self.matching;
+        let query = matching.fold(&normalized_str);
+        let query_key = matching.fold(&candidate_key(&normalized_str));
+
+        // Every path a linked file is reachable under resolves exactly, but for key lookups
+        // the file counts once. Hidden snippets only resolve by their full path.
+        let walked = self.walk()?;
+        if let Some(literal) = walked.iter().find(|snippet| snippet.relative_path == normalized_str)
+        {
+            return Ok(literal.clone());
         }
+        let mut exact_matches: Vec<SnippetEntry> = walked
+            .iter()
+            .filter(|snippet| matching.fold(&snippet.relative_path) == query)
+            .cloned()
+            .collect();
 
         if exact_matches.len() == 1 {
             return Ok(exact_matches.remove(0));
@@ -105,27 +405,60 @@ impl SnippetCatalog for FilesystemSnippetCatalog {
             ))));
         }
 
-        if key_matches.is_empty() {
-            return Err(AppError::NotFound(NotFoundError::Snippet(format!(
-                "No snippet named '{raw_query}' under {}",
-                self.commands_root.display()
-            ))));
-        }
+        // Only candidates are checked for `hidden`, so a lookup reads a few headers at most.
+        let candidates = dedupe_linked_files(walked);
+        let mut key_matches: Vec<SnippetEntry> = candidates
+            .iter()
+            .filter(|snippet| {
+                let key = matching.fold(&snippet.key);
+                key == query || key == query_key
+            })
+            .filter(|snippet| !is_hidden(snippet))
+            .cloned()
+            .collect();
 
         if key_matches.len() > 1 {
+            if let Some(preferred) = self.preferred_match(&key_matches) {
+                return Ok(key_matches.remove(preferred));
+            }
             return Err(AppError::ConfigError(ConfigError::DuplicateSnippet(format!(
                 "Multiple snippets share the name '{raw_query}': {}",
                 Self::join_paths(&key_matches)
             ))));
         }
 
-        Ok(key_matches.remove(0))
+        if let Some(found) = key_matches.pop() {
+            return Ok(found);
+        }
+
+        let mut abbreviated: Vec<SnippetEntry> = if matching.abbreviations {
+            candidates
+                .into_iter()
+                .filter(|snippet| matching.abbreviates(&normalized_str, &snippet.relative_path))
+                .filter(|snippet| !is_hidden(snippet))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        match abbreviated.len() {
+            0 => Err(AppError::NotFound(NotFoundError::Snippet(format!(
+                "No snippet named '{raw_query}' under {}",
+                self.commands_root.display()
+            )))),
+            1 => Ok(abbreviated.remove(0)),
+            _ => Err(AppError::ConfigError(ConfigError::DuplicateSnippet(format!(
+                "Multiple snippets match the abbreviation '{raw_query}': {}",
+                Self::join_paths(&abbreviated)
+            )))),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::snippets::UsageEvent;
     use std::fs;
     use tempfile::tempdir;
 
@@ -165,7 +498,7 @@ mod tests {
             fs::write(path, "content").unwrap();
         }
 
-        (FilesystemSnippetCatalog { commands_root: commands }, dir)
+        (FilesystemSnippetCatalog::new(commands), dir)
     }
 
     #[test]
@@ -175,6 +508,156 @@ mod tests {
         assert_eq!(catalog.resolve_snippet("wc").unwrap().relative_path, "w/wc");
     }
 
+    #[test]
+    fn ranking_settles_shared_names_only_with_a_clear_favourite() {
+        let (catalog, _dir) = create_catalog(&["a/wc.md", "b/wc.md"]);
+        assert!(catalog.resolve_snippet("wc").is_err());
+
+        let event = |relative_path: &str| UsageEvent {
+            relative_path: relative_path.to_string(),
+            key: "wc".to_string(),
+            used_at: 0,
+            workspace: None,
+        };
+        let tied = UsageStats::from_events(&[event("a/wc"), event("b/wc")], 0);
+        assert!(catalog.clone().with_ranking(tied).resolve_snippet("wc").is_err());
+
+        let favoured = UsageStats::from_events(&[event("b/wc"), event("b/wc"), event("a/wc")], 0);
+        let ranked = catalog.with_ranking(favoured);
+        assert_eq!(ranked.resolve_snippet("wc").unwrap().relative_path, "b/wc");
+    }
+
+    #[test]
+    fn skips_dot_directories_and_mxignore_patterns() {
+        let (catalog, dir) = create_catalog(&[
+            "w/wc.md",
+            "README.md",
+            "drafts/idea.md",
+            ".github/PULL_REQUEST_TEMPLATE.md",
+        ]);
+        fs::write(dir.path().join("commands/.mxignore"), "README.md\ndrafts/\n").unwrap();
+
+        let paths: Vec<String> =
+            catalog.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(paths, vec!["w/wc"]);
+        assert!(catalog.resolve_snippet("drafts/idea").is_err());
+    }
+
+    #[test]
+    fn hidden_snippets_resolve_only_by_full_path() {
+        let (catalog, dir) = create_catalog(&["w/wc.md"]);
+        fs::create_dir_all(dir.path().join("commands/partials")).unwrap();
+        fs::write(dir.path().join("commands/partials/wc.md"), "---\nhidden: true\n---\nx").unwrap();
+
+        let visible: Vec<String> =
+            catalog.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(visible, vec!["w/wc"]);
+        assert_eq!(catalog.enumerate_all_snippets().unwrap().len(), 2);
+        assert_eq!(catalog.resolve_snippet("wc").unwrap().relative_path, "w/wc");
+        assert_eq!(catalog.resolve_snippet("partials/wc").unwrap().relative_path, "partials/wc");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinked_directories_once_and_survives_cycles() {
+        use std::os::unix::fs::symlink;
+
+        let (catalog, dir) = create_catalog(&["w/wc.md"]);
+        let catalog = catalog.with_follow_symlinks(true);
+        let team = dir.path().join("team-repo");
+        fs::create_dir_all(&team).unwrap();
+        fs::write(team.join("review.md"), "content").unwrap();
+        let commands = dir.path().join("commands");
+        symlink(&team, commands.join("team")).unwrap();
+        symlink(&team, commands.join("x-team")).unwrap();
+        symlink(&commands, commands.join("w/loop")).unwrap();
+        symlink(dir.path().join("missing"), commands.join("dangling")).unwrap();
+
+        let paths: Vec<String> =
+            catalog.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(paths, vec!["team/review", "w/wc"]);
+        assert_eq!(catalog.resolve_snippet("review").unwrap().relative_path, "team/review");
+        assert_eq!(
+            catalog.resolve_snippet("x-team/review").unwrap().relative_path,
+            "x-team/review"
+        );
+
+        let literal = catalog.with_follow_symlinks(false);
+        let paths: Vec<String> =
+            literal.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(paths, vec!["w/wc"]);
+    }
+
+    #[test]
+    fn resolves_unambiguous_abbreviations_and_lists_ambiguous_ones() {
+        let (catalog, _dir) =
+            create_catalog(&["sdd/sdd-0-rq.md", "sdd/sdd-1-design.md", "sdd/sdd-1-tasks.md"]);
+        assert!(catalog.resolve_snippet("s/0-rq").is_err());
+        let catalog = catalog
+            .with_matching(QueryMatching { abbreviations: true, ..QueryMatching::default() });
+
+        assert_eq!(catalog.resolve_snippet("s/0-rq").unwrap().relative_path, "sdd/sdd-0-rq");
+        assert_eq!(catalog.resolve_snippet("s/1-t").unwrap().relative_path, "sdd/sdd-1-tasks");
+
+        let err = catalog.resolve_snippet("s/1").unwrap_err();
+        assert!(matches!(err, AppError::ConfigError(ConfigError::DuplicateSnippet(_))));
+        assert!(err.to_string().contains("sdd/sdd-1-design, sdd/sdd-1-tasks"));
+    }
+
+    #[test]
+    fn matches_across_unicode_normalization_and_optionally_case() {
+        let nfd = "\u{30ab}\u{3099}\u{30a4}\u{30c9}.md";
+        let (catalog, _dir) = create_catalog(&["Review/Plan.md", nfd]);
+
+        assert!(catalog.resolve_snippet("\u{30ac}\u{30a4}\u{30c9}").is_ok());
+        assert!(catalog.resolve_snippet("review/plan").is_err());
+
+        let loose = catalog
+            .with_matching(QueryMatching { case_insensitive: true, ..QueryMatching::default() });
+        assert_eq!(loose.resolve_snippet("review/plan").unwrap().relative_path, "Review/Plan");
+        assert_eq!(loose.resolve_snippet("PLAN").unwrap().relative_path, "Review/Plan");
+    }
+
+    #[test]
+    fn groups_language_variants_under_one_key_and_localizes_them() {
+        let (catalog, _dir) =
+            create_catalog(&["w/wc.md", "w/wc.ja.md", "w/wc.en.md", "t/deploy.sh.md"]);
+
+        let paths: Vec<String> =
+            catalog.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(paths, vec!["t/deploy.sh", "w/wc"]);
+
+        let wc = catalog.resolve_snippet("wc").unwrap();
+        assert!(wc.absolute_path.ends_with("w/wc.md"));
+        let japanese = catalog.clone().with_languages(vec!["ja-JP".into(), "ja".into()]);
+        assert!(japanese.localize(wc.clone()).absolute_path.ends_with("w/wc.ja.md"));
+        let german = catalog.clone().with_languages(vec!["de".into()]);
+        assert!(german.localize(wc).absolute_path.ends_with("w/wc.md"));
+
+        assert!(catalog.resolve_snippet("deploy").is_err());
+        let deploy = german.resolve_snippet("t/deploy.sh").unwrap();
+        assert!(german.localize(deploy).absolute_path.ends_with("t/deploy.sh.md"));
+    }
+
+    #[test]
+    fn enumerates_mounted_sources_under_their_namespace() {
+        let (mut catalog, dir) = create_catalog(&["w/wc.md"]);
+        let checkout = dir.path().join("sources/team");
+        fs::create_dir_all(checkout.join("review")).unwrap();
+        fs::create_dir_all(checkout.join(".git")).unwrap();
+        fs::write(checkout.join("review/security.md"), "content").unwrap();
+        fs::write(checkout.join(".git/notes.md"), "ignored").unwrap();
+        catalog.mounts.push(SourceMount { namespace: "team".to_string(), root: checkout });
+
+        let paths: Vec<String> =
+            catalog.enumerate_snippets().unwrap().into_iter().map(|e| e.relative_path).collect();
+        assert_eq!(paths, vec!["team/review/security", "w/wc"]);
+        assert_eq!(
+            catalog.resolve_snippet("security").unwrap().relative_path,
+            "team/review/security"
+        );
+    }
+
     #[test]
     #[serial_test::serial]
     fn from_env_accepts_direct_commands_root() {
@@ -1,48 +1,98 @@
 use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
-/// Strips the YAML front matter from `content` and returns the body.
-/// If no valid front matter exists, returns `content` unchanged.
-pub fn strip_frontmatter(content: &str) -> &str {
-    if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
-        return content;
+/// File name suffix of a sidecar metadata file: `wc.md` is described by `wc.meta.yaml`.
+pub const SIDECAR_SUFFIX: &str = ".meta.yaml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// `---` fences around YAML.
+    Yaml,
+    /// `+++` fences around TOML, as written for Hugo.
+    Toml,
+}
+
+impl FrontmatterFormat {
+    fn fence(self) -> &'static str {
+        match self {
+            Self::Yaml => "---",
+            Self::Toml => "+++",
+        }
     }
+}
 
-    let open_len = if content.starts_with("---\r\n") { 5 } else { 4 };
-    let after_open = &content[open_len..];
+#[derive(Debug, thiserror::Error)]
+pub enum FrontmatterError {
+    #[error("{0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("{message}")]
+    Toml { message: String, line: Option<usize> },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
 
-    if let Some(close_pos) = find_closing_fence(after_open) {
-        let fence_and_after = &after_open[close_pos..];
-        let fence_len = if fence_and_after.starts_with("---\r\n") {
-            5
-        } else if fence_and_after.starts_with("---\n") {
-            4
-        } else {
-            3 // "---" at end of file without trailing newline
-        };
-        &after_open[close_pos + fence_len..]
-    } else {
-        content
+impl FrontmatterError {
+    /// 1-based line of the problem within the metadata block, when known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::Yaml(err) => err.location().map(|location| location.line()),
+            Self::Toml { line, .. } => *line,
+            Self::Io(_) => None,
+        }
+    }
+
+    fn toml(err: toml::de::Error, source: &str) -> Self {
+        let line = err.span().map(|span| source[..span.start].matches('\n').count() + 1);
+        Self::Toml { message: err.message().to_string(), line }
     }
 }
 
-/// Returns the YAML string inside front matter, or `None` if absent / malformed.
+struct FrontmatterBlock<'a> {
+    format: FrontmatterFormat,
+    raw: &'a str,
+    body: &'a str,
+}
+
+fn split_frontmatter(content: &str) -> Option<FrontmatterBlock<'_>> {
+    [FrontmatterFormat::Yaml, FrontmatterFormat::Toml].into_iter().find_map(|format| {
+        let fence = format.fence();
+        let after_open = content
+            .strip_prefix(fence)
+            .and_then(|rest| rest.strip_prefix('\n').or_else(|| rest.strip_prefix("\r\n")))?;
+        let close_pos = find_closing_fence(after_open, fence)?;
+        let after_close = &after_open[close_pos + fence.len()..];
+        let body = after_close
+            .strip_prefix('\n')
+            .or_else(|| after_close.strip_prefix("\r\n"))
+            .unwrap_or(after_close);
+        Some(FrontmatterBlock { format, raw: &after_open[..close_pos], body })
+    })
+}
+
+/// Strips the YAML (`---`) or TOML (`+++`) front matter from `content` and returns the body.
+/// If no valid front matter exists, returns `content` unchanged.
+pub fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content).map_or(content, |block| block.body)
+}
+
+/// Returns the text inside the front matter fences, or `None` if absent / malformed.
 pub fn parse_frontmatter(content: &str) -> Option<&str> {
-    if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
-        return None;
-    }
+    split_frontmatter(content).map(|block| block.raw)
+}
 
-    let open_len = if content.starts_with("---\r\n") { 5 } else { 4 };
-    let after_open = &content[open_len..];
-    let close_pos = find_closing_fence(after_open)?;
-    Some(&after_open[..close_pos])
+/// The format of the front matter at the start of `content`, if any.
+pub fn frontmatter_format(content: &str) -> Option<FrontmatterFormat> {
+    split_frontmatter(content).map(|block| block.format)
 }
 
-fn find_closing_fence(text: &str) -> Option<usize> {
+fn find_closing_fence(text: &str, fence: &str) -> Option<usize> {
     let mut start = 0;
     loop {
         let end = text[start..].find('\n').map(|i| start + i).unwrap_or(text.len());
         let line = &text[start..end];
-        if line.strip_suffix('\r').unwrap_or(line) == "---" {
+        if line.strip_suffix('\r').unwrap_or(line) == fence {
             return Some(start);
         }
         if end == text.len() {
@@ -53,7 +103,9 @@ fn find_closing_fence(text: &str) -> Option<usize> {
     None
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+/// Typed snippet metadata. Every field is optional; keys outside this schema are reported by
+/// [`validate_frontmatter`] rather than rejected.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct SnippetFrontmatter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -61,15 +113,339 @@ pub struct SnippetFrontmatter {
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aliases: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Inputs that should exist and be non-empty before the snippet is copied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires: Option<Vec<Requirement>>,
+    /// Free-form version of the prompt itself, e.g. `1.2.0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Grouping used for browsing, e.g. `review` or `planning`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Model the prompt was written and tuned for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Position within its category; lower values come first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    /// Marks a snippet that is only meant to be included by other snippets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    /// What the prompt asks the model to produce, e.g. `markdown` or `diff`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Snippet whose body this one renders, replacing the `{{#block}}`s it overrides.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+}
+
+impl SnippetFrontmatter {
+    /// The deprecation notice, unless the snippet is not deprecated or says `deprecated: false`.
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecated.as_ref().filter(|deprecation| deprecation.is_deprecated())
+    }
+
+    /// Fills the fields this snippet leaves unset from the snippet it extends. `hidden` and
+    /// `deprecated` describe the file itself, so a hidden or retired base does not pass them on.
+    pub fn inherit(self, parent: SnippetFrontmatter) -> SnippetFrontmatter {
+        SnippetFrontmatter {
+            title: self.title.or(parent.title),
+            description: self.description.or(parent.description),
+            aliases: self.aliases.or(parent.aliases),
+            tags: self.tags.or(parent.tags),
+            requires: self.requires.or(parent.requires),
+            version: self.version.or(parent.version),
+            author: self.author.or(parent.author),
+            category: self.category.or(parent.category),
+            model: self.model.or(parent.model),
+            order: self.order.or(parent.order),
+            hidden: self.hidden,
+            deprecated: self.deprecated,
+            output: self.output.or(parent.output),
+            extends: self.extends,
+        }
+    }
+}
+
+/// An entry of `requires:`. A plain string is a context key (`tk`, `rq`, `docs/spec`) resolved
+/// inside `.mx/`; `{path: ...}` names a file relative to the workspace root.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Requirement {
+    Context(String),
+    WorkspaceFile { path: String },
+}
+
+/// `deprecated: true`, or a mapping that points at the snippet to use instead.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Deprecation {
+    Flag(bool),
+    Details {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
+impl Deprecation {
+    pub fn is_deprecated(&self) -> bool {
+        !matches!(self, Self::Flag(false))
+    }
+
+    pub fn replacement(&self) -> Option<&str> {
+        match self {
+            Self::Details { replacement, .. } => replacement.as_deref(),
+            Self::Flag(_) => None,
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Details { reason, .. } => reason.as_deref(),
+            Self::Flag(_) => None,
+        }
+    }
+}
+
+/// Front matter keys understood by [`SnippetFrontmatter`].
+pub const KNOWN_FRONTMATTER_FIELDS: &[&str] = &[
+    "title",
+    "description",
+    "aliases",
+    "tags",
+    "requires",
+    "version",
+    "author",
+    "category",
+    "model",
+    "order",
+    "hidden",
+    "deprecated",
+    "output",
+    "extends",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontmatterWarning {
+    /// 1-based line within the file holding the metadata, when the warning concerns a key.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedFrontmatter {
+    pub metadata: SnippetFrontmatter,
+    pub warnings: Vec<FrontmatterWarning>,
+}
+
+/// Parses the front matter of `content` and collects problems that do not prevent parsing,
+/// such as keys outside the schema. Returns `Ok(None)` when there is no front matter.
+pub fn validate_frontmatter(
+    content: &str,
+) -> Result<Option<ValidatedFrontmatter>, FrontmatterError> {
+    let Some(block) = split_frontmatter(content) else {
+        return Ok(None);
+    };
+    // The block starts on the line after the opening fence.
+    validate_block(block.format, block.raw, 1).map(Some)
+}
+
+/// Like [`validate_frontmatter`] for the sidecar of the snippet at `snippet_path`. Returns
+/// `Ok(None)` when there is no sidecar.
+pub fn validate_sidecar(
+    snippet_path: &Path,
+) -> Result<Option<ValidatedFrontmatter>, FrontmatterError> {
+    let Some(yaml) = read_sidecar(snippet_path)? else {
+        return Ok(None);
+    };
+    validate_block(FrontmatterFormat::Yaml, &yaml, 0).map(Some)
 }
 
 pub fn parse_frontmatter_metadata(
     content: &str,
-) -> Result<Option<SnippetFrontmatter>, serde_yaml::Error> {
-    let Some(yaml) = parse_frontmatter(content) else {
+) -> Result<Option<SnippetFrontmatter>, FrontmatterError> {
+    let Some(block) = split_frontmatter(content) else {
         return Ok(None);
     };
-    Ok(Some(serde_yaml::from_str(yaml)?))
+    parse_block(block.format, block.raw).map(Some)
+}
+
+/// Metadata of the snippet at `snippet_path`: its own front matter when it has one, otherwise
+/// its `.meta.yaml` sidecar.
+pub fn read_snippet_metadata(
+    snippet_path: &Path,
+    content: &str,
+) -> Result<Option<SnippetFrontmatter>, FrontmatterError> {
+    if let Some(metadata) = parse_frontmatter_metadata(content)? {
+        return Ok(Some(metadata));
+    }
+    match read_sidecar(snippet_path)? {
+        Some(yaml) => parse_block(FrontmatterFormat::Yaml, &yaml).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Rewrites the `extends:` reference in the front matter of `content` when `rewrite` returns a
+/// replacement for it. Returns `None` when nothing changed.
+pub fn rewrite_extends<F>(content: &str, rewrite: F) -> Option<String>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let block = split_frontmatter(content)?;
+    let start = block.raw.as_ptr() as usize - content.as_ptr() as usize;
+    let end = start + block.raw.len();
+    let rewritten = rewrite_extends_lines(block.raw, rewrite)?;
+    Some(format!("{}{rewritten}{}", &content[..start], &content[end..]))
+}
+
+/// Like [`rewrite_extends`], for the contents of a `.meta.yaml` sidecar.
+pub fn rewrite_sidecar_extends<F>(yaml: &str, rewrite: F) -> Option<String>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    rewrite_extends_lines(yaml, rewrite)
+}
+
+/// Rewrites a top-level `extends: value` (YAML) or `extends = "value"` (TOML) line, keeping
+/// its quoting.
+fn rewrite_extends_lines<F>(text: &str, mut rewrite: F) -> Option<String>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut changed = false;
+    let lines: Vec<String> = text
+        .split_inclusive('\n')
+        .map(|line| {
+            let replaced = line.strip_prefix("extends").and_then(|rest| {
+                let value_start = rest.find([':', '='])?;
+                if !rest[..value_start].trim().is_empty() {
+                    return None;
+                }
+                let raw = rest[value_start + 1..].trim();
+                let quote = raw.chars().next().filter(|c| *c == '"' || *c == '\'');
+                let value = match quote {
+                    Some(quote) => raw.strip_prefix(quote)?.strip_suffix(quote)?,
+                    None => raw,
+                };
+                let replacement = rewrite(value)?;
+                let quote = quote.map(String::from).unwrap_or_default();
+                let separator = &rest[..=value_start];
+                let ending = &line[line.trim_end_matches(['\n', '\r']).len()..];
+                Some(format!("extends{separator} {quote}{replacement}{quote}{ending}"))
+            });
+            changed |= replaced.is_some();
+            replaced.unwrap_or_else(|| line.to_string())
+        })
+        .collect();
+    changed.then(|| lines.concat())
+}
+
+/// Like [`read_snippet_metadata`], but reads the snippet only up to the end of its front
+/// matter instead of loading the whole file.
+pub fn read_snippet_header(
+    snippet_path: &Path,
+) -> Result<Option<SnippetFrontmatter>, FrontmatterError> {
+    let mut reader = BufReader::new(File::open(snippet_path)?);
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    let opening = header.trim_end_matches(['\n', '\r']).to_string();
+    let fenced = [FrontmatterFormat::Yaml, FrontmatterFormat::Toml]
+        .into_iter()
+        .any(|format| format.fence() == opening);
+    if fenced {
+        loop {
+            let start = header.len();
+            if reader.read_line(&mut header)? == 0
+                || header[start..].trim_end_matches(['\n', '\r']) == opening
+            {
+                break;
+            }
+        }
+    }
+    read_snippet_metadata(snippet_path, &header)
+}
+
+/// `w/wc.md` -> `w/wc.meta.yaml`.
+pub fn sidecar_path(snippet_path: &Path) -> PathBuf {
+    let stem = snippet_path.file_stem().unwrap_or_default().to_string_lossy();
+    snippet_path.with_file_name(format!("{stem}{SIDECAR_SUFFIX}"))
+}
+
+fn read_sidecar(snippet_path: &Path) -> Result<Option<String>, FrontmatterError> {
+    match fs::read_to_string(sidecar_path(snippet_path)) {
+        Ok(yaml) => Ok(Some(yaml)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn parse_block(
+    format: FrontmatterFormat,
+    raw: &str,
+) -> Result<SnippetFrontmatter, FrontmatterError> {
+    match format {
+        // An empty YAML document is `null`, which means "no fields" here.
+        FrontmatterFormat::Yaml => {
+            Ok(serde_yaml::from_str::<Option<SnippetFrontmatter>>(raw)?.unwrap_or_default())
+        }
+        FrontmatterFormat::Toml => {
+            toml::from_str(raw).map_err(|err| FrontmatterError::toml(err, raw))
+        }
+    }
+}
+
+fn validate_block(
+    format: FrontmatterFormat,
+    raw: &str,
+    line_offset: usize,
+) -> Result<ValidatedFrontmatter, FrontmatterError> {
+    let metadata = parse_block(format, raw)?;
+    let keys: Vec<String> = match format {
+        FrontmatterFormat::Yaml => serde_yaml::from_str::<Option<serde_yaml::Mapping>>(raw)?
+            .unwrap_or_default()
+            .keys()
+            .filter_map(|key| key.as_str().map(str::to_string))
+            .collect(),
+        FrontmatterFormat::Toml => toml::from_str::<toml::Table>(raw)
+            .map_err(|err| FrontmatterError::toml(err, raw))?
+            .keys()
+            .cloned()
+            .collect(),
+    };
+
+    let separator = match format {
+        FrontmatterFormat::Yaml => ':',
+        FrontmatterFormat::Toml => '=',
+    };
+    let warnings = keys
+        .iter()
+        .filter(|key| !KNOWN_FRONTMATTER_FIELDS.contains(&key.as_str()))
+        .map(|field| FrontmatterWarning {
+            line: raw
+                .lines()
+                .position(|line| {
+                    line.strip_prefix(field.as_str())
+                        .is_some_and(|rest| rest.trim_start().starts_with(separator))
+                })
+                .map(|index| index + 1 + line_offset),
+            message: format!("unknown frontmatter field '{field}'"),
+        })
+        .collect();
+
+    Ok(ValidatedFrontmatter { metadata, warnings })
+}
+
+/// Renders `metadata` as a YAML front matter block, including both fences.
+pub fn render_frontmatter(metadata: &SnippetFrontmatter) -> Result<String, serde_yaml::Error> {
+    Ok(format!("---\n{}---\n", serde_yaml::to_string(metadata)?))
 }
 
 #[cfg(test)]
@@ -134,8 +510,173 @@ mod tests {
         assert!(fm.description.is_none());
     }
 
+    #[test]
+    fn validate_frontmatter_warns_about_unknown_keys_with_lines() {
+        let validated =
+            validate_frontmatter("---\ntitle: T\ncolour: red\n---\nbody\n").unwrap().unwrap();
+        assert_eq!(validated.metadata.title.as_deref(), Some("T"));
+        assert_eq!(
+            validated.warnings,
+            vec![FrontmatterWarning {
+                line: Some(3),
+                message: "unknown frontmatter field 'colour'".to_string(),
+            }]
+        );
+        assert!(validate_frontmatter("---\n---\nbody").unwrap().unwrap().warnings.is_empty());
+        assert!(validate_frontmatter("no frontmatter").unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_frontmatter_metadata_reads_lifecycle_fields() {
+        let content = "---\nversion: 1.2.0\nauthor: platform\ncategory: review\nmodel: opus\n\
+                       order: 3\nhidden: true\noutput: markdown\n\
+                       deprecated: {replacement: review/security, reason: merged}\n---\nbody\n";
+        let fm = parse_frontmatter_metadata(content).unwrap().unwrap();
+        assert_eq!(fm.version.as_deref(), Some("1.2.0"));
+        assert_eq!(fm.category.as_deref(), Some("review"));
+        assert_eq!(fm.order, Some(3));
+        assert_eq!(fm.hidden, Some(true));
+        assert_eq!(fm.output.as_deref(), Some("markdown"));
+        let deprecation = fm.deprecation().unwrap();
+        assert_eq!(deprecation.replacement(), Some("review/security"));
+        assert_eq!(deprecation.reason(), Some("merged"));
+    }
+
+    #[test]
+    fn deprecated_flag_accepts_booleans() {
+        let deprecated =
+            parse_frontmatter_metadata("---\ndeprecated: true\n---\n").unwrap().unwrap();
+        assert!(deprecated.deprecation().is_some());
+        assert_eq!(deprecated.deprecation().unwrap().replacement(), None);
+
+        let current = parse_frontmatter_metadata("---\ndeprecated: false\n---\n").unwrap().unwrap();
+        assert!(current.deprecation().is_none());
+    }
+
+    #[test]
+    fn inherit_prefers_the_child_and_keeps_hidden_to_the_parent() {
+        let parent = parse_frontmatter_metadata(
+            "---\ntitle: Review\ntags: [review]\nmodel: opus\nhidden: true\n---\n",
+        )
+        .unwrap()
+        .unwrap();
+        let child = parse_frontmatter_metadata("---\nextends: base/review\ntitle: Security\n---\n")
+            .unwrap()
+            .unwrap();
+
+        let merged = child.inherit(parent);
+
+        assert_eq!(merged.title.as_deref(), Some("Security"));
+        assert_eq!(merged.tags, Some(vec!["review".to_string()]));
+        assert_eq!(merged.model.as_deref(), Some("opus"));
+        assert_eq!(merged.hidden, None);
+        assert_eq!(merged.extends.as_deref(), Some("base/review"));
+    }
+
+    #[test]
+    fn render_frontmatter_round_trips_through_parser() {
+        let metadata = SnippetFrontmatter {
+            title: Some("Security pass".to_string()),
+            tags: Some(vec!["review".to_string()]),
+            ..SnippetFrontmatter::default()
+        };
+        let rendered = format!("{}body\n", render_frontmatter(&metadata).unwrap());
+
+        assert_eq!(parse_frontmatter_metadata(&rendered).unwrap(), Some(metadata));
+        assert_eq!(strip_frontmatter(&rendered), "body\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_metadata_reads_context_and_path_requirements() {
+        let content = "---\nrequires: [tk, {path: docs/spec.md}]\n---\nbody\n";
+        let fm = parse_frontmatter_metadata(content).unwrap().unwrap();
+        assert_eq!(
+            fm.requires,
+            Some(vec![
+                Requirement::Context("tk".to_string()),
+                Requirement::WorkspaceFile { path: "docs/spec.md".to_string() },
+            ])
+        );
+    }
+
     #[test]
     fn parse_frontmatter_metadata_none_when_no_frontmatter() {
         assert!(parse_frontmatter_metadata("no frontmatter").unwrap().is_none());
     }
+
+    #[test]
+    fn strips_and_parses_toml_frontmatter() {
+        let content = "+++\ntitle = \"T\"\ntags = [\"a\"]\n+++\nbody\n";
+        assert_eq!(strip_frontmatter(content), "body\n");
+        assert_eq!(frontmatter_format(content), Some(FrontmatterFormat::Toml));
+        let fm = parse_frontmatter_metadata(content).unwrap().unwrap();
+        assert_eq!(fm.title.as_deref(), Some("T"));
+        assert_eq!(fm.tags, Some(vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn validate_frontmatter_reports_toml_unknown_keys_and_errors_with_lines() {
+        let validated =
+            validate_frontmatter("+++\ntitle = \"T\"\ncolour = \"red\"\n+++\n").unwrap().unwrap();
+        assert_eq!(validated.warnings[0].line, Some(3));
+
+        let err = validate_frontmatter("+++\ntitle = \"T\"\norder = \"x\"\n+++\n").unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn read_snippet_metadata_prefers_frontmatter_over_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wc.md");
+        fs::write(sidecar_path(&path), "title: Sidecar\ncolour: red\n").unwrap();
+        assert_eq!(sidecar_path(&path), dir.path().join("wc.meta.yaml"));
+
+        let from_sidecar = read_snippet_metadata(&path, "body").unwrap().unwrap();
+        assert_eq!(from_sidecar.title.as_deref(), Some("Sidecar"));
+        let validated = validate_sidecar(&path).unwrap().unwrap();
+        assert_eq!(validated.warnings[0].line, Some(2));
+
+        let embedded = read_snippet_metadata(&path, "---\ntitle: Own\n---\nbody").unwrap();
+        assert_eq!(embedded.unwrap().title.as_deref(), Some("Own"));
+        assert!(read_snippet_metadata(&dir.path().join("other.md"), "body").unwrap().is_none());
+    }
+
+    #[test]
+    fn read_snippet_header_stops_at_the_closing_fence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wc.md");
+        let mut content = b"---\nhidden: true\n---\n".to_vec();
+        content.extend_from_slice(&[0xff, 0xfe]);
+        fs::write(&path, content).unwrap();
+        assert_eq!(read_snippet_header(&path).unwrap().unwrap().hidden, Some(true));
+
+        fs::write(&path, "body\n").unwrap();
+        fs::write(sidecar_path(&path), "title: Sidecar\n").unwrap();
+        let from_sidecar = read_snippet_header(&path).unwrap().unwrap();
+        assert_eq!(from_sidecar.title.as_deref(), Some("Sidecar"));
+    }
+
+    #[test]
+    fn rewrite_extends_replaces_the_reference_and_keeps_quoting() {
+        let rename = |query: &str| (query == "base/review").then(|| "team/review".to_string());
+
+        assert_eq!(
+            rewrite_extends(
+                "---\ntitle: T\nextends: base/review\n---\nbody extends: base/review\n",
+                rename
+            )
+            .unwrap(),
+            "---\ntitle: T\nextends: team/review\n---\nbody extends: base/review\n"
+        );
+        assert_eq!(
+            rewrite_extends("+++\nextends = \"base/review\"\n+++\n", rename).unwrap(),
+            "+++\nextends = \"team/review\"\n+++\n"
+        );
+        assert_eq!(
+            rewrite_sidecar_extends("extends: 'base/review'\n", rename).unwrap(),
+            "extends: 'team/review'\n"
+        );
+        assert!(rewrite_extends("---\nextends: other\n---\n", rename).is_none());
+        assert!(rewrite_extends("extends: base/review\n", rename).is_none());
+    }
 }
@@ -0,0 +1,281 @@
+use crate::error::{AppError, ConfigError, NotFoundError};
+use crate::project_fs::move_file;
+use crate::timestamp::unix_now;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const INDEX_FILE: &str = "index.json";
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How many snapshots are kept per snippet. The newest snapshot is never pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPolicy {
+    pub max_revisions: usize,
+    pub max_age_days: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self { max_revisions: 20, max_age_days: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revision {
+    pub id: u64,
+    /// Seconds since the Unix epoch.
+    pub recorded_at: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryIndex {
+    /// Modification time (nanoseconds since the epoch) of the snippet when it was last observed.
+    #[serde(default)]
+    modified: Option<u128>,
+    #[serde(default)]
+    revisions: Vec<Revision>,
+}
+
+/// Snapshot store for snippet contents, laid out as `<root>/<relative path>/<id>.md` with an
+/// `index.json` per snippet.
+#[derive(Debug, Clone)]
+pub struct SnippetHistory {
+    root: PathBuf,
+    retention: RetentionPolicy,
+}
+
+impl SnippetHistory {
+    pub fn new(root: PathBuf, retention: RetentionPolicy) -> Self {
+        Self { root, retention }
+    }
+
+    /// Records the current contents of `path` when its modification time differs from the one
+    /// seen last time. Contents identical to the newest snapshot are not stored twice.
+    pub fn observe(
+        &self,
+        relative_path: &str,
+        path: &Path,
+        reason: &str,
+    ) -> Result<Option<Revision>, AppError> {
+        let modified = fs::metadata(path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+
+        let mut index = self.load_index(relative_path)?;
+        if index.modified == Some(modified) && !index.revisions.is_empty() {
+            return Ok(None);
+        }
+        index.modified = Some(modified);
+
+        let content = fs::read_to_string(path)?;
+        if let Some(latest) = index.revisions.last() {
+            if self.read_revision(relative_path, latest.id)? == content {
+                self.save_index(relative_path, &index)?;
+                return Ok(None);
+            }
+        }
+
+        let id = index.revisions.last().map_or(1, |latest| latest.id + 1);
+        let revision = Revision { id, recorded_at: unix_now(), reason: reason.to_string() };
+        let dir = self.snippet_dir(relative_path);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(format!("{id}.md")), content)?;
+        index.revisions.push(revision.clone());
+
+        self.prune(relative_path, &mut index)?;
+        self.save_index(relative_path, &index)?;
+        Ok(Some(revision))
+    }
+
+    /// Lists the snapshots of a snippet, oldest first.
+    pub fn revisions(&self, relative_path: &str) -> Result<Vec<Revision>, AppError> {
+        Ok(self.load_index(relative_path)?.revisions)
+    }
+
+    pub fn read_revision(&self, relative_path: &str, id: u64) -> Result<String, AppError> {
+        let path = self.snippet_dir(relative_path).join(format!("{id}.md"));
+        fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => AppError::NotFound(NotFoundError::File(format!(
+                "No revision {id} recorded for {relative_path}"
+            ))),
+            _ => err.into(),
+        })
+    }
+
+    /// Moves the snapshots of `from` so they follow a snippet that was renamed to `to`.
+    /// Snapshots left at `to` by a removed snippet are discarded. Only the snippets' own files
+    /// are touched; nested directories belong to other snippets.
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), AppError> {
+        let source = self.snippet_dir(from);
+        if !source.join(INDEX_FILE).is_file() {
+            return Ok(());
+        }
+
+        let target = self.snippet_dir(to);
+        for path in own_files(&target)? {
+            fs::remove_file(path)?;
+        }
+        for path in own_files(&source)? {
+            if let Some(name) = path.file_name() {
+                move_file(&path, &target.join(name))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn prune(&self, relative_path: &str, index: &mut HistoryIndex) -> Result<(), AppError> {
+        let now = unix_now();
+        let max_age = self.retention.max_age_days.map(|days| days * SECONDS_PER_DAY);
+        let max_revisions = self.retention.max_revisions.max(1);
+        let dir = self.snippet_dir(relative_path);
+
+        while let Some(oldest) = index.revisions.first() {
+            let too_many = index.revisions.len() > max_revisions;
+            let too_old = index.revisions.len() > 1
+                && max_age.is_some_and(|max_age| now.saturating_sub(oldest.recorded_at) > max_age);
+            if !too_many && !too_old {
+                break;
+            }
+
+            match fs::remove_file(dir.join(format!("{}.md", oldest.id))) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            index.revisions.remove(0);
+        }
+        Ok(())
+    }
+
+    fn snippet_dir(&self, relative_path: &str) -> PathBuf {
+        self.root.join(relative_path)
+    }
+
+    fn load_index(&self, relative_path: &str) -> Result<HistoryIndex, AppError> {
+        let path = self.snippet_dir(relative_path).join(INDEX_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|err| {
+                AppError::ConfigError(ConfigError::Other(format!(
+                    "Corrupt history index {}: {err}",
+                    path.display()
+                )))
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(HistoryIndex::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save_index(&self, relative_path: &str, index: &HistoryIndex) -> Result<(), AppError> {
+        let dir = self.snippet_dir(relative_path);
+        fs::create_dir_all(&dir)?;
+        let content = serde_json::to_string_pretty(index)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        fs::write(dir.join(INDEX_FILE), content)?;
+        Ok(())
+    }
+}
+
+fn own_files(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+
+    fn set_modified(path: &Path, seconds: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+    }
+
+    #[test]
+    fn observe_records_changes_and_skips_unchanged_files() {
+        let dir = tempdir().unwrap();
+        let snippet = dir.path().join("wc.md");
+        fs::write(&snippet, "v1").unwrap();
+        let history = SnippetHistory::new(dir.path().join("history"), RetentionPolicy::default());
+
+        let first = history.observe("w/wc", &snippet, "observed").unwrap();
+        assert_eq!(first.map(|revision| revision.id), Some(1));
+        assert!(history.observe("w/wc", &snippet, "observed").unwrap().is_none());
+
+        fs::write(&snippet, "v2").unwrap();
+        set_modified(&snippet, 1_000);
+        let second = history.observe("w/wc", &snippet, "edit").unwrap().unwrap();
+        assert_eq!(second.id, 2);
+        assert_eq!(second.reason, "edit");
+        assert_eq!(history.read_revision("w/wc", 1).unwrap(), "v1");
+        assert_eq!(history.read_revision("w/wc", 2).unwrap(), "v2");
+    }
+
+    #[test]
+    fn observe_ignores_touched_files_with_identical_content() {
+        let dir = tempdir().unwrap();
+        let snippet = dir.path().join("wc.md");
+        fs::write(&snippet, "same").unwrap();
+        let history = SnippetHistory::new(dir.path().join("history"), RetentionPolicy::default());
+
+        history.observe("w/wc", &snippet, "observed").unwrap();
+        set_modified(&snippet, 2_000);
+        assert!(history.observe("w/wc", &snippet, "observed").unwrap().is_none());
+        assert_eq!(history.revisions("w/wc").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn observe_prunes_beyond_max_revisions() {
+        let dir = tempdir().unwrap();
+        let snippet = dir.path().join("wc.md");
+        let policy = RetentionPolicy { max_revisions: 2, max_age_days: None };
+        let history = SnippetHistory::new(dir.path().join("history"), policy);
+
+        for (version, seconds) in [("v1", 10), ("v2", 20), ("v3", 30)] {
+            fs::write(&snippet, version).unwrap();
+            set_modified(&snippet, seconds);
+            history.observe("w/wc", &snippet, "observed").unwrap();
+        }
+
+        let ids: Vec<u64> = history.revisions("w/wc").unwrap().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert!(history.read_revision("w/wc", 1).is_err());
+    }
+
+    #[test]
+    fn rename_moves_only_the_snippets_own_snapshots() {
+        let dir = tempdir().unwrap();
+        let history = SnippetHistory::new(dir.path().join("history"), RetentionPolicy::default());
+        let parent = dir.path().join("parent.md");
+        let child = dir.path().join("child.md");
+        fs::write(&parent, "parent").unwrap();
+        fs::write(&child, "child").unwrap();
+        history.observe("w/wc", &parent, "observed").unwrap();
+        history.observe("w/wc/nested", &child, "observed").unwrap();
+
+        history.rename("w/wc", "work/wc").unwrap();
+
+        assert_eq!(history.read_revision("work/wc", 1).unwrap(), "parent");
+        assert_eq!(history.read_revision("w/wc/nested", 1).unwrap(), "child");
+        assert!(history.revisions("w/wc").unwrap().is_empty());
+    }
+}
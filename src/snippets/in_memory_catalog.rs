@@ -1,5 +1,10 @@
 use crate::error::AppError;
-use crate::snippets::{SnippetCatalog, SnippetEntry};
+use crate::snippets::{
+    candidate_key, RetentionPolicy, SnippetCatalog, SnippetEntry, SnippetHistory,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
 
 pub struct InMemoryCatalog {
     entries: Vec<SnippetEntry>,
@@ -9,6 +14,36 @@ impl InMemoryCatalog {
     pub fn new(entries: Vec<SnippetEntry>) -> Self {
         Self { entries }
     }
+
+    /// Writes each `(relative path, content)` pair to `<root>/<relative path>.md` and catalogs
+    /// the files.
+    pub fn with_files(root: &Path, files: &[(&str, &str)]) -> Self {
+        let entries = files
+            .iter()
+            .map(|(relative_path, content)| {
+                let path = root.join(format!("{relative_path}.md"));
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, content).unwrap();
+                SnippetEntry {
+                    key: candidate_key(relative_path),
+                    relative_path: relative_path.to_string(),
+                    absolute_path: path,
+                }
+            })
+            .collect();
+        Self::new(entries)
+    }
+}
+
+/// [`InMemoryCatalog::with_files`] in a fresh temporary directory, kept alive by the caller.
+pub fn catalog_with_files(files: &[(&str, &str)]) -> (InMemoryCatalog, TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    (InMemoryCatalog::with_files(dir.path(), files), dir)
+}
+
+/// Snippet history under `<root>/history` with the default retention policy.
+pub fn history_in(root: &Path) -> SnippetHistory {
+    SnippetHistory::new(root.join("history"), RetentionPolicy::default())
 }
 
 impl SnippetCatalog for InMemoryCatalog {
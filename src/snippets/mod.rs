@@ -1,18 +1,46 @@
+mod blocks;
 mod catalog_entry;
 mod filesystem_catalog;
 mod frontmatter;
+mod history;
+mod pack;
+mod pins;
+mod placeholders;
 mod query;
 mod snippet_catalog;
+mod usage;
+mod variants;
 
 #[cfg(test)]
 mod in_memory_catalog;
 #[cfg(test)]
-pub use in_memory_catalog::InMemoryCatalog;
+pub use in_memory_catalog::{catalog_with_files, history_in, InMemoryCatalog};
 
+pub use blocks::{block_overrides, render_blocks, BlockError};
 pub use catalog_entry::SnippetEntry;
-pub use filesystem_catalog::FilesystemSnippetCatalog;
+pub use filesystem_catalog::{CatalogConfig, FilesystemSnippetCatalog};
 pub use frontmatter::{
-    parse_frontmatter, parse_frontmatter_metadata, strip_frontmatter, SnippetFrontmatter,
+    frontmatter_format, parse_frontmatter, parse_frontmatter_metadata, read_snippet_header,
+    read_snippet_metadata, render_frontmatter, rewrite_extends, rewrite_sidecar_extends,
+    sidecar_path, strip_frontmatter, validate_frontmatter, validate_sidecar, Deprecation,
+    FrontmatterError, FrontmatterFormat, FrontmatterWarning, Requirement, SnippetFrontmatter,
+    ValidatedFrontmatter, KNOWN_FRONTMATTER_FIELDS, SIDECAR_SUFFIX,
+};
+pub use history::{RetentionPolicy, Revision, SnippetHistory};
+pub use pack::{
+    read_pack, sha256_hex, write_pack, PackEntry, PackFile, PackManifest, PackVariant, SnippetPack,
+    PACK_FORMAT,
+};
+pub use pins::{parse_pin_slot, validate_slot, Pin, PinStore, MAX_PIN_SLOT};
+pub use placeholders::{
+    classify_placeholder, rewrite_snippet_includes, scan_placeholders, Placeholder,
+    PlaceholderToken, BLOCK_END, BLOCK_START_PREFIX, SNIPPET_INCLUDE_PREFIX,
+};
+pub use query::{
+    candidate_key, conflicting_snippets, normalize_query, path_to_string, QueryMatching,
+};
+pub use snippet_catalog::{CatalogProblem, SnippetCatalog};
+pub use usage::{SnippetUsage, UsageConfig, UsageEvent, UsageLog, UsageStats};
+pub use variants::{
+    preferred_languages, split_language, variant_files, variant_language, variant_path,
 };
-pub use query::{candidate_key, normalize_query, path_to_string};
-pub use snippet_catalog::SnippetCatalog;
@@ -0,0 +1,275 @@
+use crate::error::{AppError, ConfigError};
+use crate::snippets::variants::is_language_tag;
+use crate::snippets::{normalize_query, SIDECAR_SUFFIX};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+/// Pack layout version written to and accepted from `manifest.json`.
+pub const PACK_FORMAT: u32 = 1;
+const MANIFEST_PATH: &str = "manifest.json";
+const SNIPPETS_DIR: &str = "snippets/";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub format: u32,
+    pub version: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub created_at: u64,
+    pub entries: Vec<PackEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackEntry {
+    /// Snippet path relative to the commands root, without the `.md` extension.
+    pub path: String,
+    pub sha256: String,
+    /// Checksum of the `.meta.yaml` sidecar, when the snippet has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<PackVariant>,
+}
+
+/// A language variant of a pack entry, stored as `snippets/<path>.<language>.md`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackVariant {
+    pub language: String,
+    pub sha256: String,
+}
+
+/// A snippet file carried by a pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackFile {
+    pub relative_path: String,
+    pub content: String,
+    /// Contents of the snippet's `.meta.yaml` sidecar.
+    pub metadata: Option<String>,
+    /// Language and contents of each variant.
+    pub variants: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnippetPack {
+    pub manifest: PackManifest,
+    pub files: Vec<PackFile>,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Writes `manifest.json` followed by one `snippets/<path>.md` member per file, and a
+/// `snippets/<path>.meta.yaml` or `snippets/<path>.<language>.md` member for each sidecar and
+/// variant.
+pub fn write_pack<W: Write>(writer: W, pack: &SnippetPack) -> Result<(), AppError> {
+    let manifest = serde_json::to_vec_pretty(&pack.manifest).map_err(invalid_pack)?;
+    let mut builder = tar::Builder::new(writer);
+    append_member(&mut builder, MANIFEST_PATH, &manifest, pack.manifest.created_at)?;
+    for file in &pack.files {
+        let member = format!("{SNIPPETS_DIR}{}.md", file.relative_path);
+        append_member(&mut builder, &member, file.content.as_bytes(), pack.manifest.created_at)?;
+        if let Some(metadata) = &file.metadata {
+            let member = format!("{SNIPPETS_DIR}{}{SIDECAR_SUFFIX}", file.relative_path);
+            append_member(&mut builder, &member, metadata.as_bytes(), pack.manifest.created_at)?;
+        }
+        for (language, content) in &file.variants {
+            let member = format!("{SNIPPETS_DIR}{}.{language}.md", file.relative_path);
+            append_member(&mut builder, &member, content.as_bytes(), pack.manifest.created_at)?;
+        }
+    }
+    builder.into_inner()?.flush()?;
+    Ok(())
+}
+
+/// Reads a pack and checks it against its manifest: every entry must be present with a
+/// matching checksum, and no member may point outside the commands root.
+pub fn read_pack<R: Read>(reader: R) -> Result<SnippetPack, AppError> {
+    let mut archive = tar::Archive::new(reader);
+    let mut manifest = None;
+    let mut contents = BTreeMap::new();
+
+    for member in archive.entries()? {
+        let mut member = member?;
+        if !member.header().entry_type().is_file() {
+            continue;
+        }
+        let name = member.path()?.to_string_lossy().replace('\\', "/");
+        let mut bytes = Vec::new();
+        member.read_to_end(&mut bytes)?;
+
+        if name == MANIFEST_PATH {
+            manifest = Some(serde_json::from_slice::<PackManifest>(&bytes).map_err(invalid_pack)?);
+        } else if let Some(path) = name.strip_prefix(SNIPPETS_DIR) {
+            contents.insert(path.to_string(), bytes);
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| invalid_pack(format!("missing {MANIFEST_PATH}")))?;
+    if manifest.format != PACK_FORMAT {
+        return Err(invalid_pack(format!("unsupported format {}", manifest.format)));
+    }
+
+    let mut files = Vec::with_capacity(manifest.entries.len());
+    for entry in &manifest.entries {
+        let relative_path = normalize_query(&entry.path)?.to_string();
+        if relative_path != entry.path {
+            return Err(invalid_pack(format!("entry path '{}' is not normalized", entry.path)));
+        }
+
+        let content = take_member(&mut contents, &format!("{relative_path}.md"), &entry.sha256)?;
+        let metadata = match &entry.metadata_sha256 {
+            Some(sha256) => Some(take_member(
+                &mut contents,
+                &format!("{relative_path}{SIDECAR_SUFFIX}"),
+                sha256,
+            )?),
+            None => None,
+        };
+        let mut variants = Vec::with_capacity(entry.variants.len());
+        for variant in &entry.variants {
+            if !is_language_tag(&variant.language) {
+                return Err(invalid_pack(format!(
+                    "'{}' is not a language tag of '{relative_path}'",
+                    variant.language
+                )));
+            }
+            let member = format!("{relative_path}.{}.md", variant.language);
+            let content = take_member(&mut contents, &member, &variant.sha256)?;
+            variants.push((variant.language.clone(), content));
+        }
+        files.push(PackFile { relative_path, content, metadata, variants });
+    }
+
+    if let Some(extra) = contents.keys().next() {
+        return Err(invalid_pack(format!("file '{extra}' is not listed in the manifest")));
+    }
+
+    Ok(SnippetPack { manifest, files })
+}
+
+/// Removes the member at `path` from `contents`, checking it against `sha256`.
+fn take_member(
+    contents: &mut BTreeMap<String, Vec<u8>>,
+    path: &str,
+    sha256: &str,
+) -> Result<String, AppError> {
+    let bytes =
+        contents.remove(path).ok_or_else(|| invalid_pack(format!("missing file '{path}'")))?;
+    if sha256_hex(&bytes) != sha256 {
+        return Err(invalid_pack(format!("checksum mismatch for '{path}'")));
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_pack(format!("'{path}' is not valid UTF-8")))
+}
+
+fn append_member<W: Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    bytes: &[u8],
+    modified: u64,
+) -> Result<(), AppError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(modified);
+    header.set_cksum();
+    builder.append_data(&mut header, path, bytes)?;
+    Ok(())
+}
+
+fn invalid_pack(message: impl ToString) -> AppError {
+    AppError::ConfigError(ConfigError::InvalidPack(message.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pack() -> SnippetPack {
+        let content = "# /wc\nPlan critically\n".to_string();
+        let metadata = "tags: [team]\n".to_string();
+        let japanese = "# /wc\n批判的に計画する\n".to_string();
+        SnippetPack {
+            manifest: PackManifest {
+                format: PACK_FORMAT,
+                version: "1.0.0".to_string(),
+                author: Some("platform".to_string()),
+                created_at: 1_700_000_000,
+                entries: vec![PackEntry {
+                    path: "w/wc".to_string(),
+                    sha256: sha256_hex(content.as_bytes()),
+                    metadata_sha256: Some(sha256_hex(metadata.as_bytes())),
+                    variants: vec![PackVariant {
+                        language: "ja".to_string(),
+                        sha256: sha256_hex(japanese.as_bytes()),
+                    }],
+                }],
+            },
+            files: vec![PackFile {
+                relative_path: "w/wc".to_string(),
+                content,
+                metadata: Some(metadata),
+                variants: vec![("ja".to_string(), japanese)],
+            }],
+        }
+    }
+
+    #[test]
+    fn write_and_read_round_trip() {
+        let pack = sample_pack();
+        let mut bytes = Vec::new();
+        write_pack(&mut bytes, &pack).unwrap();
+
+        let read = read_pack(bytes.as_slice()).unwrap();
+        assert_eq!(read.manifest, pack.manifest);
+        assert_eq!(read.files, pack.files);
+    }
+
+    #[test]
+    fn read_rejects_checksum_mismatch() {
+        let mut pack = sample_pack();
+        pack.files[0].content = "tampered".to_string();
+        let mut bytes = Vec::new();
+        write_pack(&mut bytes, &pack).unwrap();
+
+        let err = read_pack(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch for 'w/wc.md'"));
+
+        let mut pack = sample_pack();
+        pack.files[0].metadata = Some("tags: [other]\n".to_string());
+        let mut bytes = Vec::new();
+        write_pack(&mut bytes, &pack).unwrap();
+
+        let err = read_pack(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch for 'w/wc.meta.yaml'"));
+    }
+
+    #[test]
+    fn read_rejects_traversal_entries() {
+        let mut pack = sample_pack();
+        pack.manifest.entries[0].path = "../escape".to_string();
+        let mut bytes = Vec::new();
+        write_pack(&mut bytes, &pack).unwrap();
+
+        assert!(read_pack(bytes.as_slice()).is_err());
+
+        let mut pack = sample_pack();
+        pack.manifest.entries[0].variants[0].language = "/../escape".to_string();
+        let mut bytes = Vec::new();
+        write_pack(&mut bytes, &pack).unwrap();
+
+        let err = read_pack(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("is not a language tag"));
+    }
+
+    #[test]
+    fn sha256_hex_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
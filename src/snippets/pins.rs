@@ -0,0 +1,181 @@
+use crate::error::{AppError, ConfigError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Slots are single digits so a pinned snippet is always one keystroke away: `mx c 1`.
+pub const MAX_PIN_SLOT: u8 = 9;
+
+/// A pinned snippet. The key and alias let the pin find the snippet again after it was moved
+/// without `mx mv`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pin {
+    pub path: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+/// Pinned snippets by slot, stored in `~/.config/mx/pins.yaml`.
+#[derive(Debug, Clone)]
+pub struct PinStore {
+    path: PathBuf,
+}
+
+impl PinStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn pins(&self) -> Result<BTreeMap<u8, Pin>, AppError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => return Err(err.into()),
+        };
+        if content.trim().is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        serde_yaml::from_str(&content).map_err(|err| {
+            AppError::ConfigError(ConfigError::InvalidConfigFile {
+                path: self.path.display().to_string(),
+                message: err.to_string(),
+            })
+        })
+    }
+
+    pub fn get(&self, slot: u8) -> Result<Option<Pin>, AppError> {
+        Ok(self.pins()?.remove(&slot))
+    }
+
+    /// Pins `pin` to `slot` and returns the pin it replaced.
+    pub fn set(&self, slot: u8, pin: Pin) -> Result<Option<Pin>, AppError> {
+        validate_slot(slot)?;
+        let mut pins = self.pins()?;
+        let replaced = pins.insert(slot, pin);
+        self.save(&pins)?;
+        Ok(replaced)
+    }
+
+    pub fn remove(&self, slot: u8) -> Result<Option<Pin>, AppError> {
+        let mut pins = self.pins()?;
+        let removed = pins.remove(&slot);
+        if removed.is_some() {
+            self.save(&pins)?;
+        }
+        Ok(removed)
+    }
+
+    /// Points pins at a snippet that was moved from `from` to `to`.
+    pub fn rename(&self, from: &str, to: &str, key: &str) -> Result<(), AppError> {
+        let mut pins = self.pins()?;
+        let mut changed = false;
+        for pin in pins.values_mut().filter(|pin| pin.path == from) {
+            pin.path = to.to_string();
+            pin.key = key.to_string();
+            changed = true;
+        }
+        if changed {
+            self.save(&pins)?;
+        }
+        Ok(())
+    }
+
+    /// Unpins a snippet that was removed and returns the slots it held.
+    pub fn remove_path(&self, path: &str) -> Result<Vec<u8>, AppError> {
+        let mut pins = self.pins()?;
+        let slots: Vec<u8> =
+            pins.iter().filter(|(_, pin)| pin.path == path).map(|(slot, _)| *slot).collect();
+        if !slots.is_empty() {
+            pins.retain(|_, pin| pin.path != path);
+            self.save(&pins)?;
+        }
+        Ok(slots)
+    }
+
+    fn save(&self, pins: &BTreeMap<u8, Pin>) -> Result<(), AppError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_yaml::to_string(pins)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+pub fn validate_slot(slot: u8) -> Result<(), AppError> {
+    if (1..=MAX_PIN_SLOT).contains(&slot) {
+        return Ok(());
+    }
+    Err(AppError::ConfigError(ConfigError::Other(format!(
+        "Pin slots are numbered 1 to {MAX_PIN_SLOT} (got {slot})"
+    ))))
+}
+
+/// The slot named by a query such as `1` or `:1`.
+pub fn parse_pin_slot(query: &str) -> Option<u8> {
+    let trimmed = query.trim();
+    let digits = trimmed.strip_prefix(':').unwrap_or(trimmed);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn pin(path: &str) -> Pin {
+        Pin {
+            path: path.to_string(),
+            key: path.rsplit('/').next().unwrap().to_string(),
+            alias: None,
+        }
+    }
+
+    #[test]
+    fn set_remove_and_rename_round_trip_through_the_file() {
+        let dir = tempdir().unwrap();
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+        assert!(store.pins().unwrap().is_empty());
+
+        assert_eq!(store.set(1, pin("w/wc")).unwrap(), None);
+        assert_eq!(store.set(1, pin("a/aa")).unwrap(), Some(pin("w/wc")));
+        store.set(2, pin("w/wc")).unwrap();
+        store.rename("w/wc", "work/wc2", "wc2").unwrap();
+
+        assert_eq!(store.get(1).unwrap(), Some(pin("a/aa")));
+        assert_eq!(store.get(2).unwrap(), Some(pin("work/wc2")));
+        assert_eq!(store.remove(1).unwrap(), Some(pin("a/aa")));
+        assert_eq!(store.pins().unwrap().keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert!(store.set(10, pin("w/wc")).is_err());
+    }
+
+    #[test]
+    fn remove_path_clears_every_slot_of_the_snippet() {
+        let dir = tempdir().unwrap();
+        let store = PinStore::new(dir.path().join("pins.yaml"));
+        store.set(1, pin("w/wc")).unwrap();
+        store.set(2, pin("a/aa")).unwrap();
+        store.set(3, pin("w/wc")).unwrap();
+
+        assert_eq!(store.remove_path("w/wc").unwrap(), vec![1, 3]);
+        assert!(store.remove_path("w/wc").unwrap().is_empty());
+        assert_eq!(store.pins().unwrap().keys().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn parse_pin_slot_accepts_plain_and_colon_prefixed_numbers() {
+        assert_eq!(parse_pin_slot("1"), Some(1));
+        assert_eq!(parse_pin_slot(":3"), Some(3));
+        assert_eq!(parse_pin_slot("w/1"), None);
+        assert_eq!(parse_pin_slot(":"), None);
+        assert_eq!(parse_pin_slot("+1"), None);
+        assert_eq!(parse_pin_slot("wc"), None);
+    }
+}
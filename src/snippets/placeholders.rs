@@ -0,0 +1,135 @@
+/// Token prefix marking a `{{snippet:<query>}}` include of another snippet.
+pub const SNIPPET_INCLUDE_PREFIX: &str = "snippet:";
+/// Token prefix opening a `{{#block <name>}}` that snippets extending this one can override.
+pub const BLOCK_START_PREFIX: &str = "#block ";
+/// Token closing the innermost open block.
+pub const BLOCK_END: &str = "/block";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder<'a> {
+    /// `{{relative/path.md}}`: a file under the current workspace.
+    WorkspaceFile(&'a str),
+    /// `{{snippet:w/wc}}`: another snippet resolved through the catalog.
+    SnippetInclude(&'a str),
+    /// `{{#block checklist}}`: start of an overridable block.
+    BlockStart(&'a str),
+    /// `{{/block}}`: end of the innermost open block.
+    BlockEnd,
+}
+
+/// A `{{...}}` occurrence found by [`scan_placeholders`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaceholderToken<'a> {
+    /// Byte offset of the opening `{{`.
+    pub offset: usize,
+    /// Text between the braces, or `None` when the placeholder is never closed.
+    pub token: Option<&'a str>,
+}
+
+/// Lists every placeholder in `content` in order of appearance, using the same splitting rules
+/// as placeholder expansion.
+pub fn scan_placeholders(content: &str) -> Vec<PlaceholderToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut parts = content.split("{{");
+    let mut offset = parts.next().map(str::len).unwrap_or_default();
+
+    for part in parts {
+        let token = part.split_once("}}").map(|(token, _)| token);
+        tokens.push(PlaceholderToken { offset, token });
+        offset += 2 + part.len();
+    }
+
+    tokens
+}
+
+/// Classifies the raw text between `{{` and `}}`.
+pub fn classify_placeholder(raw_token: &str) -> Placeholder<'_> {
+    let trimmed = raw_token.trim();
+    if trimmed == BLOCK_END {
+        return Placeholder::BlockEnd;
+    }
+    if let Some(name) = trimmed.strip_prefix(BLOCK_START_PREFIX) {
+        return Placeholder::BlockStart(name.trim());
+    }
+    match trimmed.strip_prefix(SNIPPET_INCLUDE_PREFIX) {
+        Some(query) => Placeholder::SnippetInclude(query.trim()),
+        None => Placeholder::WorkspaceFile(trimmed),
+    }
+}
+
+/// Rewrites the query of every `{{snippet:...}}` include for which `rewrite` returns a
+/// replacement. Returns `None` when nothing changed.
+pub fn rewrite_snippet_includes<F>(content: &str, mut rewrite: F) -> Option<String>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut output = String::with_capacity(content.len());
+    let mut changed = false;
+    let mut parts = content.split("{{");
+    output.push_str(parts.next().unwrap_or_default());
+
+    for part in parts {
+        output.push_str("{{");
+        let replacement = part.split_once("}}").and_then(|(token, rest)| {
+            let Placeholder::SnippetInclude(query) = classify_placeholder(token) else {
+                return None;
+            };
+            rewrite(query).map(|query| (query, rest))
+        });
+
+        match replacement {
+            Some((query, rest)) => {
+                changed = true;
+                output.push_str(SNIPPET_INCLUDE_PREFIX);
+                output.push_str(&query);
+                output.push_str("}}");
+                output.push_str(rest);
+            }
+            None => output.push_str(part),
+        }
+    }
+
+    changed.then_some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_placeholder_distinguishes_includes_from_workspace_files() {
+        assert_eq!(
+            classify_placeholder(" .mx/info.md "),
+            Placeholder::WorkspaceFile(".mx/info.md")
+        );
+        assert_eq!(classify_placeholder("snippet: w/wc"), Placeholder::SnippetInclude("w/wc"));
+        assert_eq!(classify_placeholder("#block checklist"), Placeholder::BlockStart("checklist"));
+        assert_eq!(classify_placeholder(" /block "), Placeholder::BlockEnd);
+    }
+
+    #[test]
+    fn scan_placeholders_reports_offsets_and_unclosed_tokens() {
+        let tokens = scan_placeholders("a {{x}} b {{y");
+        assert_eq!(
+            tokens,
+            vec![
+                PlaceholderToken { offset: 2, token: Some("x") },
+                PlaceholderToken { offset: 10, token: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrite_snippet_includes_only_touches_matching_includes() {
+        let content = "a {{snippet:w/wc}} b {{.mx/info.md}} c {{snippet:other}} {{snippet:w/wc";
+        let rewritten = rewrite_snippet_includes(content, |query| {
+            (query == "w/wc").then(|| "work/wc".to_string())
+        });
+
+        assert_eq!(
+            rewritten.as_deref(),
+            Some("a {{snippet:work/wc}} b {{.mx/info.md}} c {{snippet:other}} {{snippet:w/wc")
+        );
+        assert!(rewrite_snippet_includes(content, |_| None).is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::error::{AppError, ConfigError};
 use crate::project_fs::SafePath;
+use crate::snippets::SnippetEntry;
+use serde::Deserialize;
 use std::path::{Component, Path};
+use unicode_normalization::UnicodeNormalization;
 
 pub fn normalize_query(raw: &str) -> Result<SafePath, AppError> {
     let trimmed = raw.trim().trim_start_matches('/');
@@ -25,10 +28,79 @@ pub fn normalize_query(raw: &str) -> Result<SafePath, AppError> {
     Ok(safe_path)
 }
 
+/// How loosely queries are compared with snippet paths and keys, from the `catalog.matching`
+/// section of the user config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueryMatching {
+    pub case_insensitive: bool,
+    /// Compare in Unicode NFC, so names written in NFD (as macOS file names are) still match.
+    pub unicode_normalize: bool,
+    /// Accept `s/0-rq` for `sdd/sdd-0-rq` when no snippet matches the query outright. Off
+    /// unless enabled in the config.
+    pub abbreviations: bool,
+}
+
+impl Default for QueryMatching {
+    fn default() -> Self {
+        Self { case_insensitive: false, unicode_normalize: true, abbreviations: false }
+    }
+}
+
+impl QueryMatching {
+    /// The form of `text` that queries and names are compared in.
+    pub fn fold(&self, text: &str) -> String {
+        let normalized: String =
+            if self.unicode_normalize { text.nfc().collect() } else { text.to_string() };
+        if self.case_insensitive {
+            normalized.to_lowercase()
+        } else {
+            normalized
+        }
+    }
+
+    /// Whether `query` abbreviates `relative_path`: both have as many segments, and each query
+    /// segment is a prefix of the name segment read from its start or from a word boundary.
+    pub fn abbreviates(&self, query: &str, relative_path: &str) -> bool {
+        let query = self.fold(query);
+        let path = self.fold(relative_path);
+        let query_segments: Vec<&str> = query.split('/').collect();
+        let path_segments: Vec<&str> = path.split('/').collect();
+        query_segments.len() == path_segments.len()
+            && query_segments
+                .iter()
+                .zip(&path_segments)
+                .all(|(query, name)| abbreviates_segment(query, name))
+    }
+}
+
+fn abbreviates_segment(query: &str, name: &str) -> bool {
+    if query.is_empty() {
+        return false;
+    }
+    name.starts_with(query)
+        || name
+            .match_indices(['-', '_', '.', ' '])
+            .any(|(index, separator)| name[index + separator.len()..].starts_with(query))
+}
+
 pub fn candidate_key(normalized_query: &str) -> String {
     normalized_query.rsplit('/').next().unwrap_or(normalized_query).to_string()
 }
 
+/// Returns the entries that would make `relative_path` ambiguous if a snippet were added there:
+/// an entry at the same path, or one sharing its key.
+pub fn conflicting_snippets<'a>(
+    entries: &'a [SnippetEntry],
+    relative_path: &str,
+) -> Vec<&'a SnippetEntry> {
+    let key = candidate_key(relative_path);
+    entries
+        .iter()
+        .filter(|entry| entry.relative_path == relative_path || entry.key == key)
+        .collect()
+}
+
 pub fn path_to_string(path: &Path) -> Result<String, AppError> {
     let mut parts = Vec::new();
     for component in path.components() {
@@ -62,6 +134,43 @@ mod tests {
         assert!(normalize_query("foo//bar").is_err());
     }
 
+    #[test]
+    fn conflicting_snippets_matches_same_path_or_key() {
+        let entry = |relative_path: &str| SnippetEntry {
+            key: candidate_key(relative_path),
+            relative_path: relative_path.to_string(),
+            absolute_path: Path::new(relative_path).with_extension("md"),
+        };
+        let entries = vec![entry("w/wc"), entry("sdd/sdd-0-rq")];
+
+        assert_eq!(conflicting_snippets(&entries, "w/wc").len(), 1);
+        assert_eq!(conflicting_snippets(&entries, "other/wc").len(), 1);
+        assert!(conflicting_snippets(&entries, "w/wc2").is_empty());
+    }
+
+    #[test]
+    fn fold_applies_nfc_and_optional_case_folding() {
+        let nfd = "\u{30ab}\u{3099}\u{30a4}\u{30c9}"; // ガイド with a combining voiced mark
+        let nfc = "\u{30ac}\u{30a4}\u{30c9}";
+        let matching = QueryMatching::default();
+        assert_eq!(matching.fold(nfd), matching.fold(nfc));
+        assert_ne!(matching.fold("Review"), matching.fold("review"));
+
+        let loose = QueryMatching { case_insensitive: true, ..QueryMatching::default() };
+        assert_eq!(loose.fold("Review"), loose.fold("review"));
+    }
+
+    #[test]
+    fn abbreviations_match_segment_prefixes_and_word_starts() {
+        let matching = QueryMatching::default();
+        assert!(matching.abbreviates("s/0-rq", "sdd/sdd-0-rq"));
+        assert!(matching.abbreviates("s/sdd", "sdd/sdd-0-rq"));
+        assert!(matching.abbreviates("w/w", "w/wc"));
+        assert!(!matching.abbreviates("0-rq", "sdd/sdd-0-rq"));
+        assert!(!matching.abbreviates("s/dd", "sdd/sdd-0-rq"));
+        assert!(!matching.abbreviates("s/", "sdd/sdd-0-rq"));
+    }
+
     #[test]
     fn normalize_query_strips_prefix_and_extension() {
         assert_eq!(normalize_query("commands/w/wc.md").unwrap().to_string(), "w/wc");
@@ -1,7 +1,31 @@
 use crate::error::AppError;
 use crate::snippets::SnippetEntry;
 
+/// A file or directory the catalog could not walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogProblem {
+    pub path: String,
+    pub message: String,
+}
+
 pub trait SnippetCatalog {
     fn enumerate_snippets(&self) -> Result<Vec<SnippetEntry>, AppError>;
+    /// Like [`SnippetCatalog::enumerate_snippets`], but including hidden snippets. Used where
+    /// every file matters, such as validation and rewriting includes.
+    fn enumerate_all_snippets(&self) -> Result<Vec<SnippetEntry>, AppError> {
+        self.enumerate_snippets()
+    }
+    /// Like [`SnippetCatalog::enumerate_snippets`], but files and directories that cannot be
+    /// walked are returned as problems instead of failing the enumeration.
+    fn enumerate_snippets_leniently(
+        &self,
+    ) -> Result<(Vec<SnippetEntry>, Vec<CatalogProblem>), AppError> {
+        Ok((self.enumerate_snippets()?, Vec::new()))
+    }
     fn resolve_snippet(&self, raw_query: &str) -> Result<SnippetEntry, AppError>;
+    /// Points `entry` at the language variant to read, such as `wc.ja.md` for `wc`. Catalogs
+    /// without variants return it unchanged.
+    fn localize(&self, entry: SnippetEntry) -> SnippetEntry {
+        entry
+    }
 }
@@ -0,0 +1,219 @@
+use crate::error::{AppError, ConfigError};
+use crate::timestamp::unix_now;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Oldest events are dropped past this many so the log stays small enough to rewrite on
+/// every copy.
+const MAX_EVENTS: usize = 10_000;
+
+/// Whether copies are recorded, and whether the ranking may settle ambiguous snippet names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UsageConfig {
+    pub enabled: bool,
+    /// Resolve a name shared by several snippets to the one with the highest frecency.
+    pub resolve_ambiguous: bool,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self { enabled: true, resolve_ambiguous: false }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageEvent {
+    pub relative_path: String,
+    pub key: String,
+    /// Seconds since the Unix epoch.
+    pub used_at: u64,
+    /// Workspace root the snippet was copied in, when there was one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageFile {
+    #[serde(default)]
+    events: Vec<UsageEvent>,
+}
+
+/// Aggregated usage of one snippet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnippetUsage {
+    pub uses: u64,
+    pub last_used: Option<u64>,
+    pub frecency: u64,
+}
+
+/// Usage per relative path, computed at a fixed point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    by_path: BTreeMap<String, SnippetUsage>,
+}
+
+impl UsageStats {
+    pub fn from_events(events: &[UsageEvent], now: u64) -> Self {
+        let mut by_path: BTreeMap<String, SnippetUsage> = BTreeMap::new();
+        for event in events {
+            let usage = by_path.entry(event.relative_path.clone()).or_default();
+            usage.uses += 1;
+            usage.last_used = usage.last_used.max(Some(event.used_at));
+            usage.frecency += recency_weight(now.saturating_sub(event.used_at));
+        }
+        Self { by_path }
+    }
+
+    pub fn get(&self, relative_path: &str) -> SnippetUsage {
+        self.by_path.get(relative_path).copied().unwrap_or_default()
+    }
+
+    pub fn frecency(&self, relative_path: &str) -> u64 {
+        self.get(relative_path).frecency
+    }
+}
+
+/// Each use counts for less the older it is, so a burst of use last year does not outrank
+/// what is used every day now.
+fn recency_weight(age_seconds: u64) -> u64 {
+    match age_seconds / SECONDS_PER_DAY {
+        0..=3 => 100,
+        4..=13 => 70,
+        14..=30 => 50,
+        31..=89 => 30,
+        _ => 10,
+    }
+}
+
+/// Append-only record of snippet copies, stored as a single JSON file.
+#[derive(Debug, Clone)]
+pub struct UsageLog {
+    path: PathBuf,
+}
+
+impl UsageLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn record(
+        &self,
+        relative_path: &str,
+        key: &str,
+        workspace: Option<String>,
+    ) -> Result<UsageEvent, AppError> {
+        let event = UsageEvent {
+            relative_path: relative_path.to_string(),
+            key: key.to_string(),
+            used_at: unix_now(),
+            workspace,
+        };
+        let mut file = self.load()?;
+        file.events.push(event.clone());
+        let overflow = file.events.len().saturating_sub(MAX_EVENTS);
+        file.events.drain(..overflow);
+        self.save(&file)?;
+        Ok(event)
+    }
+
+    pub fn events(&self) -> Result<Vec<UsageEvent>, AppError> {
+        Ok(self.load()?.events)
+    }
+
+    pub fn stats(&self) -> Result<UsageStats, AppError> {
+        Ok(UsageStats::from_events(&self.events()?, unix_now()))
+    }
+
+    /// Points the usage of `from` at a snippet that was renamed to `to`.
+    pub fn rename(&self, from: &str, to: &str, key: &str) -> Result<(), AppError> {
+        let mut file = self.load()?;
+        let mut changed = false;
+        for event in file.events.iter_mut().filter(|event| event.relative_path == from) {
+            event.relative_path = to.to_string();
+            event.key = key.to_string();
+            changed = true;
+        }
+        if changed {
+            self.save(&file)?;
+        }
+        Ok(())
+    }
+
+    fn load(&self) -> Result<UsageFile, AppError> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|err| {
+                AppError::ConfigError(ConfigError::Other(format!(
+                    "Corrupt usage log {}: {err}",
+                    self.path.display()
+                )))
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(UsageFile::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save(&self, file: &UsageFile) -> Result<(), AppError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(file)
+            .map_err(|err| AppError::ConfigError(ConfigError::Other(err.to_string())))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn event(relative_path: &str, used_at: u64) -> UsageEvent {
+        UsageEvent {
+            relative_path: relative_path.to_string(),
+            key: relative_path.rsplit('/').next().unwrap().to_string(),
+            used_at,
+            workspace: None,
+        }
+    }
+
+    #[test]
+    fn stats_weigh_recent_uses_above_old_ones() {
+        let now = 400 * SECONDS_PER_DAY;
+        let events = vec![
+            event("old", 0),
+            event("old", SECONDS_PER_DAY),
+            event("old", 2 * SECONDS_PER_DAY),
+            event("new", now - 60),
+        ];
+
+        let stats = UsageStats::from_events(&events, now);
+
+        assert_eq!(stats.get("old").uses, 3);
+        assert_eq!(stats.get("old").last_used, Some(2 * SECONDS_PER_DAY));
+        assert_eq!(stats.frecency("old"), 30);
+        assert_eq!(stats.frecency("new"), 100);
+        assert_eq!(stats.get("unused"), SnippetUsage::default());
+    }
+
+    #[test]
+    fn record_appends_and_rename_follows_the_snippet() {
+        let dir = tempdir().unwrap();
+        let log = UsageLog::new(dir.path().join("state/usage.json"));
+
+        log.record("w/wc", "wc", Some("/work".to_string())).unwrap();
+        log.record("w/wc", "wc", None).unwrap();
+        log.rename("w/wc", "work/wc2", "wc2").unwrap();
+
+        let events = log.events().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.relative_path == "work/wc2"));
+        assert_eq!(events[0].workspace.as_deref(), Some("/work"));
+        assert_eq!(log.stats().unwrap().get("work/wc2").uses, 2);
+    }
+}
@@ -0,0 +1,151 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Splits a file stem such as `wc.ja` or `wc.pt-BR` into the snippet stem and its language.
+/// Only two-letter language codes, optionally with a two-letter region, count as a language
+/// so stems like `notes.v2` stay whole.
+pub fn split_language(stem: &str) -> (&str, Option<&str>) {
+    match stem.rsplit_once('.') {
+        Some((base, language)) if !base.is_empty() && is_language_tag(language) => {
+            (base, Some(language))
+        }
+        _ => (stem, None),
+    }
+}
+
+pub(crate) fn is_language_tag(tag: &str) -> bool {
+    let (language, region) = match tag.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (tag, None),
+    };
+    language.len() == 2
+        && language.bytes().all(|byte| byte.is_ascii_lowercase())
+        && region.is_none_or(|region| {
+            region.len() == 2 && region.bytes().all(|byte| byte.is_ascii_uppercase())
+        })
+}
+
+/// The language of the file at `path` when it is a variant: its stem ends in a language tag
+/// and the base file exists next to it. Without `t/deploy.md`, `t/deploy.sh.md` is a snippet
+/// of its own.
+pub fn variant_language(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    match split_language(stem) {
+        (base, Some(language)) if path.with_file_name(format!("{base}.md")).is_file() => {
+            Some(language.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The file of the `language` variant of the snippet whose base file is `path`:
+/// `w/wc.md` -> `w/wc.ja.md`.
+pub fn variant_path(path: &Path, language: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.{language}.md"))
+}
+
+/// Language variants stored next to the snippet whose base file is `path`, sorted by language.
+pub fn variant_files(path: &Path) -> Vec<(String, PathBuf)> {
+    let base = path.file_stem().unwrap_or_default().to_string_lossy();
+    let Some(Ok(entries)) = path.parent().map(fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut variants: Vec<(String, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|candidate| candidate.extension().and_then(|ext| ext.to_str()) == Some("md"))
+        .filter_map(|candidate| {
+            let candidate_stem = candidate.file_stem()?.to_str()?.to_string();
+            match split_language(&candidate_stem) {
+                (candidate_base, Some(language)) if candidate_base == base => {
+                    Some((language.to_string(), candidate))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    variants.sort();
+    variants
+}
+
+/// Languages to try, most preferred first: `explicit` (from `--lang`), else `MX_LANG`, else the
+/// system locale (`LC_ALL`, `LC_MESSAGES`, `LANG`). `ja_JP.UTF-8` yields `ja-JP` then `ja`.
+pub fn preferred_languages(explicit: Option<&str>) -> Vec<String> {
+    let requested =
+        explicit.map(str::to_string).or_else(|| env::var("MX_LANG").ok()).or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .into_iter()
+                .filter_map(|name| env::var(name).ok())
+                .find(|value| !value.is_empty())
+        });
+    requested.map(|value| language_candidates(&value)).unwrap_or_default()
+}
+
+fn language_candidates(locale: &str) -> Vec<String> {
+    let tag = locale.split(['.', '@']).next().unwrap_or_default().replace('_', "-");
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return Vec::new();
+    }
+    let (language, region) = match tag.split_once('-') {
+        Some((language, region)) => (language.to_lowercase(), Some(region.to_uppercase())),
+        None => (tag.to_lowercase(), None),
+    };
+    match region {
+        Some(region) => vec![format!("{language}-{region}"), language],
+        None => vec![language],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn split_language_recognizes_language_and_region_tags() {
+        assert_eq!(split_language("wc.ja"), ("wc", Some("ja")));
+        assert_eq!(split_language("wc.pt-BR"), ("wc", Some("pt-BR")));
+        assert_eq!(split_language("wc"), ("wc", None));
+        assert_eq!(split_language("notes.v2"), ("notes.v2", None));
+        assert_eq!(split_language("release.notes"), ("release.notes", None));
+        assert_eq!(split_language(".ja"), (".ja", None));
+    }
+
+    #[test]
+    fn language_candidates_parse_posix_locales() {
+        assert_eq!(language_candidates("ja_JP.UTF-8"), vec!["ja-JP", "ja"]);
+        assert_eq!(language_candidates("en"), vec!["en"]);
+        assert!(language_candidates("C.UTF-8").is_empty());
+    }
+
+    #[test]
+    fn variant_files_lists_siblings_of_the_base_file() {
+        let dir = tempdir().unwrap();
+        for name in ["wc.md", "wc.ja.md", "wc.en.md", "wcx.ja.md", "wc.meta.yaml"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let languages: Vec<String> = variant_files(&dir.path().join("wc.md"))
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect();
+        assert_eq!(languages, vec!["en", "ja"]);
+        assert_eq!(variant_path(&dir.path().join("wc.md"), "en"), dir.path().join("wc.en.md"));
+    }
+
+    #[test]
+    fn only_files_next_to_their_base_are_variants() {
+        let dir = tempdir().unwrap();
+        for name in ["wc.md", "wc.ja.md", "deploy.sh.md"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        assert_eq!(variant_language(&dir.path().join("wc.ja.md")).as_deref(), Some("ja"));
+        assert_eq!(variant_language(&dir.path().join("wc.md")), None);
+        assert_eq!(variant_language(&dir.path().join("deploy.sh.md")), None);
+        assert!(variant_files(&dir.path().join("deploy.sh.md")).is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::{AppError, ConfigError};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+
+/// A git repository of snippets declared under `sources:` in the user config. Its snippets are
+/// mounted under `<name>/` in the catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GitSource {
+    pub name: String,
+    pub url: String,
+    /// Branch or tag to follow; the remote's default branch when absent.
+    #[serde(default, rename = "ref")]
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncStatus {
+    Cloned { commit: String },
+    Updated { from: String, to: String },
+    UpToDate { commit: String },
+}
+
+/// Clones `source` into `checkout`, or fast-forwards an existing checkout to the latest commit
+/// of its ref. Local history that diverged from the remote is never overwritten.
+pub fn sync_source(source: &GitSource, checkout: &Path) -> Result<SyncStatus, AppError> {
+    let checkout_arg = checkout.to_string_lossy();
+
+    if !checkout.join(".git").exists() {
+        if let Some(parent) = checkout.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut args = vec!["clone", "--quiet"];
+        if let Some(reference) = &source.reference {
+            args.extend(["--branch", reference.as_str()]);
+        }
+        args.extend(["--", source.url.as_str(), checkout_arg.as_ref()]);
+        git(source, None, &args)?;
+        return Ok(SyncStatus::Cloned { commit: head(source, checkout)? });
+    }
+
+    let before = head(source, checkout)?;
+    git(source, Some(checkout), &["remote", "set-url", "origin", &source.url])?;
+    let reference = source.reference.as_deref().unwrap_or("HEAD");
+    git(source, Some(checkout), &["fetch", "--quiet", "origin", reference])?;
+    git(source, Some(checkout), &["merge", "--ff-only", "--quiet", "FETCH_HEAD"])?;
+    let after = head(source, checkout)?;
+
+    Ok(if before == after {
+        SyncStatus::UpToDate { commit: after }
+    } else {
+        SyncStatus::Updated { from: before, to: after }
+    })
+}
+
+fn head(source: &GitSource, checkout: &Path) -> Result<String, AppError> {
+    git(source, Some(checkout), &["rev-parse", "--short", "HEAD"])
+}
+
+fn git(source: &GitSource, dir: Option<&Path>, args: &[&str]) -> Result<String, AppError> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    let output = command.args(args).output().map_err(|err| sync_error(source, err.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(sync_error(
+            source,
+            format!("git {} exited with {}: {}", args[0], output.status, stderr.trim()),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn sync_error(source: &GitSource, message: String) -> AppError {
+    AppError::ConfigError(ConfigError::SourceSync { name: source.name.clone(), message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=mx", "-c", "user.email=mx@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    }
+
+    fn commit_file(work: &Path, name: &str, content: &str) {
+        fs::write(work.join(name), content).unwrap();
+        run_git(work, &["add", "."]);
+        run_git(work, &["commit", "--quiet", "-m", name]);
+        run_git(work, &["push", "--quiet", "origin", "HEAD:main"]);
+    }
+
+    fn bare_remote(root: &Path) -> (GitSource, PathBuf) {
+        let bare = root.join("remote.git");
+        let work = root.join("work");
+        fs::create_dir_all(&bare).unwrap();
+        fs::create_dir_all(&work).unwrap();
+        run_git(&bare, &["init", "--quiet", "--bare", "--initial-branch=main"]);
+        run_git(&work, &["init", "--quiet", "--initial-branch=main"]);
+        run_git(&work, &["remote", "add", "origin", &bare.to_string_lossy()]);
+        commit_file(&work, "wc.md", "v1");
+
+        let source = GitSource {
+            name: "team".to_string(),
+            url: format!("file://{}", bare.display()),
+            reference: Some("main".to_string()),
+        };
+        (source, work)
+    }
+
+    #[test]
+    fn sync_clones_then_fast_forwards() {
+        let dir = tempfile::tempdir().unwrap();
+        let (source, work) = bare_remote(dir.path());
+        let checkout = dir.path().join("cache/team");
+
+        assert!(matches!(sync_source(&source, &checkout).unwrap(), SyncStatus::Cloned { .. }));
+        assert_eq!(fs::read_to_string(checkout.join("wc.md")).unwrap(), "v1");
+        assert!(matches!(sync_source(&source, &checkout).unwrap(), SyncStatus::UpToDate { .. }));
+
+        commit_file(&work, "wc.md", "v2");
+        assert!(matches!(sync_source(&source, &checkout).unwrap(), SyncStatus::Updated { .. }));
+        assert_eq!(fs::read_to_string(checkout.join("wc.md")).unwrap(), "v2");
+    }
+
+    #[test]
+    fn sync_reports_git_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = GitSource {
+            name: "missing".to_string(),
+            url: format!("file://{}", dir.path().join("nope.git").display()),
+            reference: None,
+        };
+
+        let err = sync_source(&source, &dir.path().join("cache/missing")).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to sync source 'missing': git clone exited"));
+    }
+}
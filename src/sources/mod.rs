@@ -0,0 +1,3 @@
+mod git_source;
+
+pub use git_source::{sync_source, GitSource, SyncStatus};
@@ -0,0 +1,45 @@
+use crate::harness::TestContext;
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn aliases_lists_keys_by_source_and_marks_existing_files() {
+    let ctx = TestContext::new();
+    fs::write(ctx.work_dir().join(".mx.toml"), "[aliases]\napi = \"api-contract.md\"\n").unwrap();
+    ctx.setup_clipboard("tasks");
+    ctx.cli().args(["t", "tk"]).assert().success();
+
+    ctx.cli()
+        .arg("aliases")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".mx/api-contract.md  (project)"))
+        .stdout(predicate::str::is_match(r"✅ tk +\s+\.mx/tasks\.md  \(built-in\)").unwrap());
+
+    let output = ctx.cli().args(["aliases", "--json"]).output().unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let api = entries.as_array().unwrap().iter().find(|entry| entry["key"] == "api").unwrap();
+    assert_eq!(api["path"], "api-contract.md");
+    assert_eq!(api["source"], "project");
+    assert_eq!(api["exists"], false);
+}
+
+#[test]
+fn resolve_explains_pending_prefix_and_numbered_tasks() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["resolve", "pd-tk2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'pd-' prefix"))
+        .stdout(predicate::str::contains("numbered tasks file 'tk2'"))
+        .stdout(predicate::str::contains("-> .mx/pending/tasks/tasks2.md"));
+
+    let output = ctx.cli().args(["resolve", "docs/spec", "--json"]).output().unwrap();
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(outcome["path"], "docs/spec.md");
+    assert_eq!(outcome["steps"][0]["step"], "literal");
+    assert_eq!(outcome["steps"][1]["step"], "extension_added");
+    assert_eq!(outcome["exists"], false);
+}
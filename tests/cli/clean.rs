@@ -31,3 +31,29 @@ fn clean_alias_cl_works() {
 
     ctx.cli().arg("cl").assert().success().stdout(predicate::str::contains("Cleared"));
 }
+
+#[test]
+fn clean_moves_files_to_the_trash_for_restore() {
+    let ctx = TestContext::new();
+    ctx.setup_clipboard("careful requirements");
+    ctx.cli().args(["t", "rq"]).assert().success();
+    ctx.cli().arg("cl").assert().success();
+
+    ctx.cli()
+        .args(["restore", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("requirements.md  [rq]  20 B"));
+    ctx.cli().args(["ls-context"]).assert().success().stdout("(no context files)\n");
+
+    ctx.cli().args(["restore", "rq"]).assert().failure();
+    ctx.cli()
+        .args(["restore", "--context", "rq"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✅ Restored .mx/requirements.md from the trash"));
+    ctx.cli().args(["cat", "rq"]).assert().success().stdout(predicate::eq("careful requirements"));
+
+    ctx.cli().args(["restore", "--context", "rq"]).assert().failure();
+    ctx.cli().args(["restore", "--list"]).assert().success().stdout("(trash is empty)\n");
+}
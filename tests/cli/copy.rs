@@ -1,5 +1,6 @@
 use crate::harness::{install_sample_catalog, TestContext};
 use predicates::prelude::*;
+use std::fs;
 
 #[test]
 fn copy_subcommand_works() {
@@ -42,3 +43,129 @@ fn copy_subcommand_missing_snippet_fails() {
         .failure()
         .stderr(predicate::str::contains("No snippet named"));
 }
+
+#[test]
+fn copy_warns_about_missing_requirements() {
+    let ctx = TestContext::new();
+    ctx.write_snippet(
+        "impl/ip.md",
+        "---\nrequires: [pl, {path: docs/spec.md}]\n---\nImplement the plan\n",
+    );
+    let clipboard = ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli()
+        .args(["copy", "ip"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(".mx/plan.md is missing (run `mx t pl`)"))
+        .stderr(predicate::str::contains("docs/spec.md is missing"));
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "Implement the plan\n");
+}
+
+#[test]
+fn copy_strict_fails_until_requirements_exist() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("impl/ip.md", "---\nrequires: [pl]\n---\nImplement the plan\n");
+    ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli()
+        .args(["copy", "ip", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing required inputs: .mx/plan.md is missing"));
+
+    fs::create_dir_all(ctx.work_dir().join(".mx")).unwrap();
+    fs::write(ctx.work_dir().join(".mx/plan.md"), "1. Write tests\n").unwrap();
+    ctx.cli()
+        .args(["copy", "ip", "--strict"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("requires inputs").not());
+}
+
+#[test]
+fn copy_prints_deprecation_notice_with_replacement() {
+    let ctx = TestContext::new();
+    ctx.write_snippet(
+        "review/old-review.md",
+        "---\ndeprecated: {replacement: review/security, reason: merged into security}\n---\nOld\n",
+    );
+    ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli()
+        .args(["copy", "old-review"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("'old-review' is deprecated: merged into security"))
+        .stderr(predicate::str::contains("Use 'review/security' instead."));
+}
+
+#[test]
+fn copy_strips_toml_frontmatter() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("hugo.md", "+++\ntitle = \"T\"\n+++\nHugo body\n");
+    let clipboard = ctx.clipboard_file("clipboard_toml.txt");
+
+    ctx.cli().args(["copy", "hugo"]).assert().success();
+
+    assert_eq!(fs::read_to_string(clipboard).unwrap(), "Hugo body\n");
+}
+
+#[test]
+fn copy_accepts_abbreviated_paths() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let _ = ctx.clipboard_file("clipboard_abbrev.txt");
+    ctx.cli().args(["c", "s/0-rq"]).assert().failure();
+
+    fs::write(
+        ctx.home().join(".config/mx/config.yaml"),
+        "catalog:\n  matching:\n    abbreviations: true\n",
+    )
+    .unwrap();
+    ctx.cli()
+        .args(["c", "s/0-rq"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Copied 'sdd-0-rq'"));
+}
+
+#[test]
+fn copy_picks_the_language_variant_from_mx_lang_or_lang_flag() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("w/wc.md", "Plan critically\n");
+    ctx.write_snippet("w/wc.ja.md", "批判的に計画する\n");
+    let clipboard = ctx.clipboard_file("clipboard_lang.txt");
+    ctx.set_env("MX_LANG", "ja_JP.UTF-8");
+
+    ctx.cli()
+        .args(["c", "wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Copied 'wc' [ja] from w/wc"));
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "批判的に計画する\n");
+
+    ctx.cli().args(["c", "wc", "--lang", "fr"]).assert().success();
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "Plan critically\n");
+}
+
+#[test]
+fn copy_renders_snippets_that_extend_a_base_template() {
+    let ctx = TestContext::new();
+    ctx.write_snippet(
+        "base/review.md",
+        "---\ntitle: Review\nhidden: true\n---\nReview the diff.\n{{#block checklist}}\n- correctness\n{{/block}}\nReply in Markdown.\n",
+    );
+    ctx.write_snippet(
+        "review/security.md",
+        "---\nextends: base/review\n---\n{{#block checklist}}\n- injection\n- secrets\n{{/block}}\n",
+    );
+    let clipboard = ctx.clipboard_file("clipboard_extends.txt");
+
+    ctx.cli().args(["c", "security"]).assert().success();
+
+    assert_eq!(
+        fs::read_to_string(clipboard).unwrap(),
+        "Review the diff.\n- injection\n- secrets\nReply in Markdown.\n"
+    );
+}
@@ -0,0 +1,73 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+fn install_rewriting_editor(ctx: &TestContext, line: &str) {
+    let script = ctx.home().join("rewrite-editor.sh");
+    fs::write(&script, format!("#!/bin/sh\nprintf '# /wc\\n{line}\\n' > \"$1\"\n")).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    ctx.set_env("EDITOR", script.display().to_string());
+}
+
+#[test]
+fn edit_records_revisions_that_can_be_diffed_and_restored() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    install_rewriting_editor(&ctx, "Plan carelessly");
+
+    ctx.cli().args(["edit", "wc"]).assert().success();
+
+    ctx.cli()
+        .args(["history", "wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("History of w/wc"))
+        .stdout(predicate::str::contains("2  ").and(predicate::str::contains("edit (current)")))
+        .stdout(predicate::str::contains("1  ").and(predicate::str::contains("observed")));
+
+    ctx.cli()
+        .args(["diff", "wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("-Plan critically"))
+        .stdout(predicate::str::contains("+Plan carelessly"));
+
+    ctx.cli()
+        .args(["restore", "wc", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored w/wc to revision 1"));
+
+    let restored = fs::read_to_string(ctx.commands_root().join("w/wc.md")).unwrap();
+    assert_eq!(restored, "# /wc\nPlan critically\n");
+    assert!(ctx.home().join(".config/mx/history/w/wc/index.json").exists());
+}
+
+#[test]
+fn retention_policy_comes_from_user_config() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    fs::write(ctx.home().join(".config/mx/config.yaml"), "history:\n  max_revisions: 1\n").unwrap();
+    install_rewriting_editor(&ctx, "Plan carelessly");
+
+    ctx.cli().args(["edit", "wc"]).assert().success();
+
+    ctx.cli()
+        .args(["diff", "wc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No earlier revision recorded for w/wc"));
+}
+
+#[test]
+fn restore_rejects_unknown_revision() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .args(["restore", "wc", "7"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No revision 7 recorded for w/wc"));
+}
@@ -0,0 +1,42 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+
+#[test]
+fn lint_passes_for_clean_library() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .arg("lint")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 2 snippet(s): 0 error(s), 0 warning(s)"));
+}
+
+#[test]
+fn lint_fails_with_per_file_diagnostics() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("broken/bad.md", "---\ntitle: T\n---\nsee {{snippet:nowhere}}\n");
+
+    ctx.cli()
+        .arg("lint")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("broken/bad:4: error: include 'snippet:nowhere'"))
+        .stderr(predicate::str::contains("Lint found 1 error(s), 0 warning(s)"));
+}
+
+#[test]
+fn lint_deny_warnings_fails_on_warnings() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("w/wc.md", "---\ncolour: red\n---\nbody\n");
+
+    ctx.cli().arg("lint").assert().success();
+    ctx.cli()
+        .args(["lint", "--deny-warnings"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("w/wc:2: warning: unknown frontmatter field 'colour'"))
+        .stderr(predicate::str::contains("Lint found 0 error(s), 1 warning(s)"));
+}
@@ -24,3 +24,138 @@ fn list_alias_ls_works() {
         .success()
         .stdout(predicate::str::contains("wc").and(predicate::str::contains("sdd/sdd-0-rq")));
 }
+
+#[test]
+fn list_reports_broken_snippets_without_hiding_the_library() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("broken/bad.md", "---\ntitle: [unclosed\n---\nbody\n");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("w/wc"))
+        .stdout(predicate::str::contains("Skipped 1 snippet(s) with problems"))
+        .stdout(predicate::str::contains("- broken/bad: "));
+}
+
+#[test]
+fn list_strict_fails_on_broken_snippet() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("broken/bad.md", "---\ntitle: [unclosed\n---\nbody\n");
+
+    ctx.cli()
+        .args(["list", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse frontmatter in snippet"));
+}
+
+#[test]
+fn list_json_includes_entries_and_diagnostics() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("broken/bad.md", "---\ntitle: [unclosed\n---\nbody\n");
+
+    let output = ctx.cli().args(["list", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(value["diagnostics"][0]["relative_path"], "broken/bad");
+}
+
+#[test]
+fn list_reads_titles_from_toml_frontmatter_and_sidecars() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("hugo.md", "+++\ntitle = \"From TOML\"\n+++\nbody\n");
+    ctx.write_snippet("plain.md", "body\n");
+    ctx.write_snippet("plain.meta.yaml", "title: From sidecar\n");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("From TOML"))
+        .stdout(predicate::str::contains("From sidecar"))
+        .stdout(predicate::str::contains("plain.meta").not());
+}
+
+#[test]
+fn list_leaves_out_ignored_files_and_hidden_snippets() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet(".mxignore", "README.md\n");
+    ctx.write_snippet("README.md", "# My prompts\n");
+    ctx.write_snippet(".github/ISSUE_TEMPLATE.md", "issue\n");
+    ctx.write_snippet("partials/footer.md", "---\nhidden: true\n---\nThanks!\n");
+    let clipboard = ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("w/wc"))
+        .stdout(predicate::str::contains("README").not())
+        .stdout(predicate::str::contains("ISSUE_TEMPLATE").not())
+        .stdout(predicate::str::contains("footer").not());
+
+    ctx.cli().args(["copy", "footer"]).assert().failure();
+    ctx.cli().args(["copy", "partials/footer"]).assert().success();
+    assert_eq!(std::fs::read_to_string(clipboard).unwrap(), "Thanks!\n");
+}
+
+#[test]
+fn list_groups_language_variants_under_one_snippet() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("w/wc.md", "Plan critically\n");
+    ctx.write_snippet("w/wc.ja.md", "批判的に計画する\n");
+    ctx.write_snippet("w/wc.en.md", "Plan critically\n");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- wc (w/wc) [en, ja]"))
+        .stdout(predicate::str::contains("wc.ja").not());
+}
+
+#[test]
+fn list_keeps_a_dotted_name_without_a_base_as_its_own_snippet() {
+    let ctx = TestContext::new();
+    let script = ctx.write_snippet("t/deploy.sh.md", "Deploy it\n");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- deploy.sh (t/deploy.sh)\n"));
+    ctx.cli()
+        .args(["which", "t/deploy.sh"])
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{}\n", script.display())));
+    ctx.cli().args(["which", "deploy"]).assert().failure();
+}
+
+#[test]
+fn list_reports_a_malformed_mxignore_unless_strict() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("w/wc.md", "Plan critically\n");
+    ctx.write_snippet(".mxignore", "drafts/\na{b\n");
+    ctx.write_snippet("drafts/idea.md", "half done\n");
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- wc (w/wc)"))
+        .stdout(predicate::str::contains(".mxignore"))
+        .stdout(predicate::str::contains("idea").not());
+    ctx.cli()
+        .args(["list", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(".mxignore"));
+}
@@ -0,0 +1,37 @@
+use crate::harness::TestContext;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn ls_context_prints_a_tree_with_keys() {
+    let ctx = TestContext::new();
+    let mx_dir = ctx.work_dir().join(".mx");
+    fs::create_dir_all(mx_dir.join("pending")).unwrap();
+    fs::write(mx_dir.join("tasks.md"), "one\ntwo\n").unwrap();
+    fs::write(mx_dir.join("pending/tasks.md"), "later").unwrap();
+
+    ctx.cli()
+        .arg("ls-context")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("├── pending/\n│   └── tasks.md  [pdt]  1 line, 5 B"))
+        .stdout(predicate::str::contains("└── tasks.md  [tk]  2 lines, 8 B"));
+}
+
+#[test]
+fn ls_context_json_lists_every_file() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["ls-context"]).assert().success().stdout("(no context files)\n");
+    let mx_dir = ctx.work_dir().join(".mx");
+    fs::create_dir_all(mx_dir.join("docs")).unwrap();
+    fs::write(mx_dir.join("docs/spec.md"), "spec").unwrap();
+
+    let output = ctx.cli().args(["ls-context", "--json"]).output().unwrap();
+    let files: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(files[0]["path"], "docs/spec.md");
+    assert_eq!(files[0]["key"], Value::Null);
+    assert_eq!(files[0]["lines"], 1);
+    assert!(files[0]["modified"].is_u64());
+}
@@ -0,0 +1,116 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn edit_opens_resolved_snippet_in_editor() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.set_env("EDITOR", "touch");
+
+    ctx.cli().args(["edit", "wc"]).assert().success();
+}
+
+#[test]
+fn edit_reports_failing_editor() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.set_env("EDITOR", "false");
+
+    ctx.cli().args(["e", "wc"]).assert().failure().stderr(predicate::str::contains("exited with"));
+}
+
+#[test]
+fn mv_renames_snippet_and_updates_includes() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("r/review.md", "Start: {{snippet:w/wc}}\n");
+
+    ctx.cli()
+        .args(["mv", "wc", "work/wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Moved w/wc -> work/wc"))
+        .stdout(predicate::str::contains("Updated references in r/review"));
+
+    assert!(ctx.commands_root().join("work/wc.md").exists());
+    assert!(!ctx.commands_root().join("w").exists());
+    let review = fs::read_to_string(ctx.commands_root().join("r/review.md")).unwrap();
+    assert_eq!(review, "Start: {{snippet:work/wc}}\n");
+}
+
+#[test]
+fn rm_moves_snippet_to_trash() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli().args(["rm", "wc"]).assert().success().stdout(predicate::str::contains("trash"));
+
+    assert!(!ctx.commands_root().join("w/wc.md").exists());
+    let trash = ctx.home().join(".config/mx/trash");
+    let batch = fs::read_dir(&trash).unwrap().next().unwrap().unwrap().path();
+    assert_eq!(fs::read_to_string(batch.join("w/wc.md")).unwrap(), "# /wc\nPlan critically\n");
+
+    ctx.cli().args(["copy", "wc"]).assert().failure();
+}
+
+#[test]
+fn custom_commands_root_keeps_state_in_the_config_dir() {
+    let ctx = TestContext::new();
+    let prompts = ctx.home().join("prompts");
+    fs::create_dir_all(prompts.join("w")).unwrap();
+    fs::write(prompts.join("w/wc.md"), "Plan critically\n").unwrap();
+    ctx.set_env("MX_COMMANDS_ROOT", prompts.to_string_lossy());
+    ctx.setup_clipboard("");
+
+    ctx.cli().args(["c", "wc"]).assert().success();
+    ctx.cli().args(["rm", "wc"]).assert().success();
+
+    for leaked in ["trash", "history", "usage.json"] {
+        assert!(!ctx.home().join(leaked).exists(), "{leaked} written next to the commands root");
+    }
+    assert!(ctx.home().join(".config/mx/trash").is_dir());
+    assert!(ctx.home().join(".config/mx/history").is_dir());
+}
+
+#[test]
+fn custom_commands_root_lists_snippets_without_home() {
+    let ctx = TestContext::new();
+    let prompts = ctx.home().join("prompts");
+    fs::create_dir_all(prompts.join("w")).unwrap();
+    fs::write(prompts.join("w/wc.md"), "Plan critically\n").unwrap();
+    ctx.set_env("MX_COMMANDS_ROOT", prompts.to_string_lossy());
+
+    ctx.cli()
+        .env_remove("HOME")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("wc"));
+}
+
+#[test]
+fn invalid_config_file_is_still_reported() {
+    let ctx = TestContext::new();
+    ctx.write_snippet("w/wc.md", "Plan critically\n");
+    fs::write(ctx.home().join(".config/mx/config.yaml"), "catalog: [\n").unwrap();
+
+    ctx.cli()
+        .arg("list")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config file"));
+}
+
+#[test]
+fn mv_carries_the_sidecar_metadata_file_along() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("w/wc.meta.yaml", "title: Critical\n");
+
+    ctx.cli().args(["mv", "wc", "work/wc"]).assert().success();
+
+    assert!(!ctx.commands_root().join("w").exists());
+    let sidecar = fs::read_to_string(ctx.commands_root().join("work/wc.meta.yaml")).unwrap();
+    assert_eq!(sidecar, "title: Critical\n");
+}
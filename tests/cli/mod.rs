@@ -1,7 +1,18 @@
+mod aliases;
 mod cat;
 mod clean;
 mod copy;
 mod help_and_version;
+mod history;
+mod lint;
 mod list;
+mod ls_context;
+mod manage;
+mod new;
+mod pack;
+mod pin;
+mod search;
+mod stats;
+mod sync;
 mod touch;
 mod which;
@@ -0,0 +1,50 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn new_creates_snippet_from_stdin_with_frontmatter() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args([
+            "new",
+            "review/security-pass",
+            "--stdin",
+            "--title",
+            "Security pass",
+            "--tag",
+            "review",
+        ])
+        .write_stdin("Check every input\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created 'security-pass'"));
+
+    let content = fs::read_to_string(ctx.commands_root().join("review/security-pass.md")).unwrap();
+    assert!(content.starts_with("---\ntitle: Security pass\ntags:\n- review\n---\n"));
+    assert!(content.ends_with("Check every input\n"));
+}
+
+#[test]
+fn new_creates_snippet_from_clipboard() {
+    let ctx = TestContext::new();
+    ctx.setup_clipboard("clipboard prompt");
+
+    ctx.cli().args(["n", "notes/idea", "--from-clipboard"]).assert().success();
+
+    let content = fs::read_to_string(ctx.commands_root().join("notes/idea.md")).unwrap();
+    assert!(content.ends_with("---\nclipboard prompt"));
+}
+
+#[test]
+fn new_refuses_colliding_key() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .args(["new", "other/wc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("would collide with existing snippets: w/wc"));
+}
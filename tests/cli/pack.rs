@@ -0,0 +1,73 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn pack_round_trips_between_libraries() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let pack = ctx.home().join("team.tar");
+
+    ctx.cli()
+        .args(["pack", "export", "w", "-o"])
+        .arg(&pack)
+        .args(["--version", "1.2.0", "--author", "platform"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported 1 snippet(s) as version 1.2.0"));
+
+    let other = TestContext::new();
+    other
+        .cli()
+        .args(["pack", "import"])
+        .arg(&pack)
+        .args(["--prefix", "team/"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pack version 1.2.0 by platform"))
+        .stdout(predicate::str::contains("Added team/w/wc"));
+
+    let imported = fs::read_to_string(other.commands_root().join("team/w/wc.md")).unwrap();
+    assert_eq!(imported, "# /wc\nPlan critically\n");
+}
+
+#[test]
+fn pack_import_reports_conflicts_and_resolves_them_on_request() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let pack = ctx.home().join("team.tar");
+    ctx.cli().args(["pack", "export", "w", "-o"]).arg(&pack).assert().success();
+
+    ctx.cli()
+        .args(["pack", "import"])
+        .arg(&pack)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("w/wc (conflicts with w/wc)"));
+
+    ctx.cli()
+        .args(["pack", "import"])
+        .arg(&pack)
+        .args(["--on-conflict", "rename"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed w/wc -> w/wc-2"));
+    assert!(ctx.commands_root().join("w/wc-2.md").exists());
+
+    ctx.cli()
+        .args(["pack", "import"])
+        .arg(&pack)
+        .args(["--on-conflict", "skip"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 0 of 1 snippet(s)"));
+}
+
+#[test]
+fn pack_import_rejects_tampered_archives() {
+    let ctx = TestContext::new();
+    let pack = ctx.home().join("broken.tar");
+    fs::write(&pack, b"not a tar archive").unwrap();
+
+    ctx.cli().args(["pack", "import"]).arg(&pack).assert().failure();
+}
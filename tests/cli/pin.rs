@@ -0,0 +1,81 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn pinned_slots_are_copied_by_number() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let clipboard = ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli()
+        .args(["pin", "1", "w/wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pinned w/wc to slot 1"));
+
+    ctx.cli().args(["c", "1"]).assert().success().stdout(predicate::str::contains("Copied 'wc'"));
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "# /wc\nPlan critically\n");
+
+    ctx.cli().args(["c", ":1"]).assert().success();
+    ctx.cli()
+        .args(["c", ":2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No snippet pinned to slot 2"));
+}
+
+#[test]
+fn list_pinned_shows_slots_and_follows_moves() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.cli().args(["pin", "2", "sdd-0-rq"]).assert().success();
+    ctx.cli().args(["pin", "1", "wc"]).assert().success();
+
+    ctx.cli().args(["mv", "wc", "work/wc"]).assert().success();
+
+    let output = ctx.cli().args(["list", "--pinned"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("1: wc (work/wc)"), "{stdout}");
+    assert!(stdout.contains("2: sdd-0-rq (sdd/sdd-0-rq)"), "{stdout}");
+    assert!(stdout.find("1: wc").unwrap() < stdout.find("2: sdd-0-rq").unwrap());
+
+    ctx.cli().args(["unpin", "1"]).assert().success();
+    ctx.cli()
+        .args(["list", "--pinned"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1: wc").not());
+}
+
+#[test]
+fn rm_unpins_the_removed_snippet() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.cli().args(["pin", "1", "wc"]).assert().success();
+
+    ctx.cli()
+        .args(["rm", "wc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unpinned slot 1"));
+    ctx.write_snippet("other/wc.md", "Someone else's wc\n");
+
+    ctx.cli()
+        .args(["c", ":1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No snippet pinned to slot 1"));
+}
+
+#[test]
+fn pin_rejects_slots_outside_one_to_nine() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .args(["pin", "0", "wc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("numbered 1 to 9"));
+}
@@ -0,0 +1,36 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+
+#[test]
+fn search_prints_matching_line_with_snippet_path() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli().args(["search", "critically"]).assert().success().stdout(
+        predicate::str::contains("w/wc (wc)").and(predicate::str::contains("2: Plan critically")),
+    );
+}
+
+#[test]
+fn search_alias_s_supports_ignore_case() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .args(["s", "-i", "REQUIREMENTS"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sdd/sdd-0-rq"));
+}
+
+#[test]
+fn search_reports_no_matches() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .args(["search", "nothing-like-this"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no matches"));
+}
@@ -0,0 +1,71 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn copies_are_counted_for_stats_and_list_sorting() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let _ = ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli().args(["copy", "sdd-0-rq"]).assert().success();
+    ctx.cli().args(["copy", "wc"]).assert().success();
+    ctx.cli().args(["copy", "wc"]).assert().success();
+
+    ctx.cli()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- w/wc (wc): 2 use(s)"))
+        .stdout(predicate::str::contains("- sdd/sdd-0-rq (sdd-0-rq): 1 use(s)"));
+
+    let output = ctx.cli().args(["list", "--sort", "frequent"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.find("w/wc").unwrap() < stdout.find("sdd/sdd-0-rq").unwrap());
+}
+
+#[test]
+fn stats_lists_snippets_that_were_never_copied() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+
+    ctx.cli()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(no recorded copies yet)"))
+        .stdout(predicate::str::contains("Never copied (2)"));
+}
+
+#[test]
+fn tracking_can_be_disabled_in_config() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let _ = ctx.clipboard_file("clipboard.txt");
+    fs::write(ctx.home().join(".config/mx/config.yaml"), "usage:\n  enabled: false\n").unwrap();
+
+    ctx.cli().args(["copy", "wc"]).assert().success();
+
+    assert!(!ctx.home().join(".config/mx/usage.json").exists());
+    ctx.cli()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage tracking is disabled"));
+}
+
+#[test]
+fn ambiguous_names_resolve_to_the_most_used_snippet_when_enabled() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    ctx.write_snippet("other/wc.md", "Other wc\n");
+    let clipboard = ctx.clipboard_file("clipboard.txt");
+
+    ctx.cli().args(["copy", "w/wc"]).assert().success();
+    ctx.cli().args(["copy", "wc"]).assert().failure();
+
+    fs::write(ctx.home().join(".config/mx/config.yaml"), "usage:\n  resolve_ambiguous: true\n")
+        .unwrap();
+    ctx.cli().args(["copy", "wc"]).assert().success();
+    assert_eq!(fs::read_to_string(clipboard).unwrap(), "# /wc\nPlan critically\n");
+}
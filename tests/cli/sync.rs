@@ -0,0 +1,108 @@
+use crate::harness::{install_sample_catalog, TestContext};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=mx", "-c", "user.email=mx@example.com"])
+        .args(args)
+        .output()
+        .expect("git should run");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+fn publish(work: &Path, relative: &str, content: &str) {
+    let path = work.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+    git(work, &["add", "."]);
+    git(work, &["commit", "--quiet", "-m", relative]);
+    git(work, &["push", "--quiet", "origin", "HEAD:main"]);
+}
+
+/// Creates a bare `file://` remote with a working clone used to publish commits to it.
+fn team_remote(ctx: &TestContext) -> (String, std::path::PathBuf) {
+    let bare = ctx.home().join("remote/prompts.git");
+    let work = ctx.home().join("remote/work");
+    fs::create_dir_all(&bare).unwrap();
+    fs::create_dir_all(&work).unwrap();
+    git(&bare, &["init", "--quiet", "--bare", "--initial-branch=main"]);
+    git(&work, &["init", "--quiet", "--initial-branch=main"]);
+    git(&work, &["remote", "add", "origin", &bare.to_string_lossy()]);
+    publish(&work, "review/security.md", "Check inputs\n");
+
+    (format!("file://{}", bare.display()), work)
+}
+
+fn configure_source(ctx: &TestContext, url: &str) {
+    fs::write(
+        ctx.home().join(".config/mx/config.yaml"),
+        format!("sources:\n  - name: team\n    url: {url}\n    ref: main\n"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn sync_mounts_source_next_to_personal_snippets() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let (url, work) = team_remote(&ctx);
+    configure_source(&ctx, &url);
+
+    ctx.cli().args(["list"]).assert().success().stdout(predicate::str::contains("team/").not());
+
+    ctx.cli().arg("sync").assert().success().stdout(predicate::str::contains("team: cloned at"));
+
+    ctx.cli()
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("team/review/security"))
+        .stdout(predicate::str::contains("w/wc"));
+
+    let clipboard = ctx.clipboard_file("clipboard.txt");
+    ctx.cli().args(["copy", "team/review/security"]).assert().success();
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "Check inputs\n");
+
+    publish(&work, "review/security.md", "Check inputs twice\n");
+    ctx.cli().args(["sync", "team"]).assert().success().stdout(predicate::str::contains("updated"));
+    ctx.cli().args(["copy", "security"]).assert().success();
+    assert_eq!(fs::read_to_string(&clipboard).unwrap(), "Check inputs twice\n");
+
+    ctx.cli()
+        .args(["sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("team: already up to date"));
+}
+
+#[test]
+fn synced_snippets_are_read_only() {
+    let ctx = TestContext::new();
+    let (url, _work) = team_remote(&ctx);
+    configure_source(&ctx, &url);
+    ctx.cli().arg("sync").assert().success();
+
+    ctx.cli()
+        .args(["rm", "security"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("comes from a synced source and is read-only"));
+}
+
+#[test]
+fn sync_reports_failing_sources() {
+    let ctx = TestContext::new();
+    configure_source(&ctx, &format!("file://{}", ctx.home().join("missing.git").display()));
+
+    ctx.cli()
+        .arg("sync")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to sync source 'team'"))
+        .stderr(predicate::str::contains("1 source(s) failed to sync"));
+}
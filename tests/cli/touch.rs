@@ -37,3 +37,128 @@ fn touch_force_overwrites() {
 
     ctx.cli().args(["cat", "tk"]).assert().success().stdout(predicate::eq("new clipboard content"));
 }
+
+#[test]
+fn touch_uses_aliases_from_the_user_alias_file() {
+    let ctx = TestContext::new();
+    let aliases = ctx.home().join(".config/mx/aliases.toml");
+    fs::create_dir_all(aliases.parent().unwrap()).unwrap();
+    fs::write(&aliases, "[aliases]\nadr = \"docs/adr.md\"\ntk = false\n").unwrap();
+    ctx.setup_clipboard("decision");
+
+    ctx.cli().args(["t", "adr"]).assert().success();
+    assert_eq!(fs::read_to_string(ctx.work_dir().join(".mx/docs/adr.md")).unwrap(), "decision");
+
+    // With the built-in alias disabled, `tk` is an ordinary key.
+    ctx.cli().args(["t", "tk"]).assert().success();
+    assert!(ctx.work_dir().join(".mx/tk.md").is_file());
+
+    fs::write(&aliases, "[aliases]\nup = \"../outside.md\"\n").unwrap();
+    ctx.cli()
+        .args(["t", "up"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("aliases.toml"))
+        .stderr(predicate::str::contains("points outside .mx"));
+}
+
+#[test]
+fn project_aliases_in_mx_toml_win_over_user_aliases() {
+    let ctx = TestContext::new();
+    let user_aliases = ctx.home().join(".config/mx/aliases.toml");
+    fs::create_dir_all(user_aliases.parent().unwrap()).unwrap();
+    fs::write(&user_aliases, "[aliases]\napi = \"my-api.md\"\n").unwrap();
+    fs::write(ctx.work_dir().join(".mx.toml"), "[aliases]\napi = \"api-contract.md\"\n").unwrap();
+    ctx.setup_clipboard("openapi: 3.1");
+
+    ctx.cli().args(["t", "api"]).assert().success();
+
+    assert!(ctx.work_dir().join(".mx/api-contract.md").is_file());
+    assert!(!ctx.work_dir().join(".mx/my-api.md").exists());
+    ctx.cli().args(["ct", "api"]).assert().success().stdout(predicate::eq("openapi: 3.1"));
+}
+
+#[test]
+fn touch_append_adds_entries_with_a_separator() {
+    let ctx = TestContext::new();
+    ctx.setup_clipboard("first");
+    ctx.cli().args(["t", "er", "--append"]).assert().success();
+
+    ctx.setup_clipboard("second");
+    ctx.cli()
+        .args(["t", "er", "-a", "--separator", "rule"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✅ Appended to context file"));
+
+    assert_eq!(
+        fs::read_to_string(ctx.work_dir().join(".mx/error.md")).unwrap(),
+        "first\n\n---\n\nsecond"
+    );
+    ctx.cli().args(["t", "er", "-a", "-f"]).assert().failure();
+}
+
+#[test]
+fn touch_reads_stdin_files_and_command_output() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["t", "er", "--stdin"]).write_stdin("test failed\n").assert().success();
+    assert_eq!(fs::read_to_string(ctx.work_dir().join(".mx/error.md")).unwrap(), "test failed\n");
+
+    let log = ctx.work_dir().join("build.log");
+    fs::write(&log, "warning: unused").unwrap();
+    ctx.cli().args(["t", "wn", "--from", "build.log"]).assert().success();
+    assert_eq!(
+        fs::read_to_string(ctx.work_dir().join(".mx/warnings.md")).unwrap(),
+        "warning: unused"
+    );
+
+    ctx.cli().args(["t", "er", "-a", "--cmd", "echo second"]).assert().success();
+    assert_eq!(
+        fs::read_to_string(ctx.work_dir().join(".mx/error.md")).unwrap(),
+        "test failed\nsecond\n"
+    );
+
+    ctx.cli().args(["t", "rq", "--from", "missing.txt"]).assert().failure();
+    assert!(!ctx.work_dir().join(".mx/requirements.md").exists());
+    ctx.cli().args(["t", "wn", "-f", "--from", "missing.txt"]).assert().failure();
+    assert_eq!(
+        fs::read_to_string(ctx.work_dir().join(".mx/warnings.md")).unwrap(),
+        "warning: unused"
+    );
+    assert!(!ctx.work_dir().join(".mx/.trash").exists());
+    ctx.cli().args(["t", "rq", "--stdin", "--cmd", "echo x"]).assert().failure();
+}
+
+#[test]
+fn touch_and_cat_work_without_home() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .env_remove("HOME")
+        .args(["t", "tk", "--stdin"])
+        .write_stdin("from a pipe\n")
+        .assert()
+        .success();
+    assert_eq!(fs::read_to_string(ctx.work_dir().join(".mx/tasks.md")).unwrap(), "from a pipe\n");
+
+    ctx.cli()
+        .env_remove("HOME")
+        .args(["ct", "tk"])
+        .assert()
+        .success()
+        .stdout(predicate::eq("from a pipe\n"));
+}
+
+#[test]
+fn touch_refuses_keys_inside_the_trash() {
+    let ctx = TestContext::new();
+    ctx.setup_clipboard("sneaky");
+
+    ctx.cli()
+        .args(["t", ".trash/1700000000/tasks", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(".mx/.trash"));
+    assert!(!ctx.work_dir().join(".mx/.trash").exists());
+}
@@ -33,3 +33,32 @@ fn which_with_unknown_snippet_fails() {
         .failure()
         .stderr(predicate::str::contains("No snippet named"));
 }
+
+#[cfg(unix)]
+#[test]
+fn which_verbose_shows_the_target_of_a_symlinked_snippet() {
+    let ctx = TestContext::new();
+    install_sample_catalog(&ctx);
+    let team = ctx.work_dir().join("team-repo");
+    std::fs::create_dir_all(&team).unwrap();
+    std::fs::write(team.join("review.md"), "Review it\n").unwrap();
+    std::os::unix::fs::symlink(&team, ctx.commands_root().join("team")).unwrap();
+    std::fs::write(
+        ctx.home().join(".config/mx/config.yaml"),
+        "catalog:\n  follow_symlinks: true\n",
+    )
+    .unwrap();
+
+    let linked = ctx.commands_root().join("team/review.md").display().to_string();
+    let target = team.canonicalize().unwrap().join("review.md").display().to_string();
+    ctx.cli()
+        .args(["which", "--verbose", "review"])
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{linked} -> {target}\n")));
+    ctx.cli()
+        .args(["which", "review"])
+        .assert()
+        .success()
+        .stdout(predicate::eq(format!("{linked}\n")));
+}